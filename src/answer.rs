@@ -0,0 +1,148 @@
+//! The answer to one part of a puzzle
+//!
+//! Most puzzles want a number, some of them too big for 32 or even 64 bits;
+//! a few want text, e.g. letters drawn in ASCII art over several lines.
+//! Two answers are only equal if they're the same kind, so the number 7 isn't
+//! the text "7". Expected answers, though, are text, as written in examples and
+//! answers files, and an answer is checked against one by the text it prints.
+
+use std::fmt;
+
+use super::error::{Error, Result};
+use super::json::Json;
+
+
+/// A puzzle's answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+
+    /// Any integer up to 128 bits, which covers every u64
+    Number(i128),
+
+    /// Text, possibly over several lines
+    Text(String),
+
+}
+
+
+impl Answer {
+
+    /// Whether the answer spans several lines, e.g. ASCII art
+    pub fn is_multiline(&self) -> bool {
+        return matches!(self, Answer::Text(text) if text.contains('\n'));
+    }
+
+    /// The answer on a single line, with line breaks written as "\n", for
+    /// tables and answers files
+    ///
+    /// ```
+    /// use aoc_2021_sjunot::Answer;
+    ///
+    /// assert_eq!(Answer::from(u64::MAX).inline(), "18446744073709551615");
+    /// assert_eq!(Answer::from("#.#\n.#.").inline(), "#.#\\n.#.");
+    /// ```
+    pub fn inline(&self) -> String {
+        match self {
+            Answer::Number(n) => return n.to_string(),
+            Answer::Text(text) => return text.replace('\\', "\\\\").replace('\n', "\\n"),
+        }
+    }
+
+}
+
+
+macro_rules! from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Answer {
+                    return Answer::Number(n as i128);
+                }
+            }
+        )*
+    };
+}
+
+from_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+
+impl From<String> for Answer {
+    fn from(text: String) -> Answer {
+        return Answer::Text(text);
+    }
+}
+
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Answer {
+        return Answer::Text(String::from(text));
+    }
+}
+
+
+impl From<&Answer> for Json {
+    fn from(answer: &Answer) -> Json {
+        match answer {
+            Answer::Number(n) => return Json::Number(*n),
+            Answer::Text(text) => return Json::String(text.clone()),
+        }
+    }
+}
+
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(n) => return write!(f, "{}", n),
+            Answer::Text(text) => return f.write_str(text),
+        }
+    }
+}
+
+
+/// Compare with an expected answer as written in an example
+impl PartialEq<&str> for Answer {
+    fn eq(&self, expected: &&str) -> bool {
+        match self {
+            Answer::Number(n) => return n.to_string() == *expected,
+            Answer::Text(text) => return text == expected,
+        }
+    }
+}
+
+
+/// The result of checked arithmetic, or an error saying what overflowed
+///
+/// ```
+/// use aoc_2021_sjunot::answer::checked;
+///
+/// assert_eq!(checked(2i32.checked_mul(3), "the product").unwrap(), 6);
+/// assert!(checked(i32::MAX.checked_add(1), "the sum").is_err());
+/// ```
+pub fn checked<T>(value: Option<T>, what: &str) -> Result<T> {
+    return value.ok_or_else(|| Error::puzzle(format!("{} overflows", what)));
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare() {
+        assert_eq!(Answer::from(7u32), Answer::from(7i64));
+        assert_ne!(Answer::from(7), Answer::from("7"));
+        assert!(Answer::from(-7) == "-7");
+        let art = Answer::from("#.\n.#");
+        assert!(art == "#.\n.#");
+        assert!(art.is_multiline());
+        assert!(!Answer::from(i128::MIN).is_multiline());
+    }
+
+    #[test]
+    fn test_json() {
+        assert_eq!(Json::from(&Answer::from(u64::MAX)).to_string(), "18446744073709551615");
+        assert_eq!(Json::from(&Answer::from("a\nb")).to_string(), "\"a\\nb\"");
+    }
+
+}
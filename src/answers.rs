@@ -0,0 +1,297 @@
+//! Known-good answers kept next to the puzzle inputs
+//!
+//! The answers for an input file live beside it with an ".answers" suffix,
+//! e.g. "inputs/day01/part1.answers", with one part per line:
+//!
+//! ```text
+//! part1 1655
+//! part2 1683
+//! ```
+//!
+//! Answers are compared as text. An answer over several lines, e.g. letters in
+//! ASCII art, is written on one line with "\n" between its lines, as
+//! [`Answer::inline`] gives it.
+//!
+//! A day can also be solved against a whole directory of inputs, e.g. other
+//! people's, each checked against its own answers file if it has one.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::answer::Answer;
+use super::error::Error;
+use super::puzzle::Example;
+use super::registry::Day;
+use super::runner::{Part, Parts, Solution};
+
+
+/// Appended to the name of an input file to get its answers file
+pub const SUFFIX: &str = ".answers";
+
+
+/// The expected answers for an input; a part may be unknown
+#[derive(Debug, Default, PartialEq)]
+pub struct Expected {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+
+impl From<Example> for Expected {
+
+    fn from(example: Example) -> Expected {
+        return Expected {
+            part1: Some(Answer::from(example.part1).inline()),
+            part2: Some(Answer::from(example.part2).inline()),
+        };
+    }
+
+}
+
+
+impl Expected {
+
+    /// Parse the contents of an answers file
+    pub fn parse(source: &str) -> Result<Expected, String> {
+
+        let mut expected = Expected::default();
+        for (line_idx, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (part, answer) = line.split_once(char::is_whitespace).ok_or(
+                format!("line {}: expected 'part1 <answer>' or 'part2 <answer>'", line_idx + 1)
+            )?;
+            let answer = Some(answer.trim().to_string());
+            match part {
+                "part1" => expected.part1 = answer,
+                "part2" => expected.part2 = answer,
+                _ => return Err(format!("line {}: unknown part '{}'", line_idx + 1, part)),
+            }
+        }
+        return Ok(expected);
+
+    }
+
+    /// Load the answers kept next to an input file
+    pub fn load(input_path: &Path) -> io::Result<Expected> {
+        let path = path_for(input_path);
+        let source = fs::read_to_string(&path)?;
+        return Expected::parse(&source).map_err(
+            |e| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), e))
+        );
+    }
+
+}
+
+
+/// Where the answers for an input file are kept
+pub fn path_for(input_path: &Path) -> PathBuf {
+    let mut path = input_path.as_os_str().to_owned();
+    path.push(SUFFIX);
+    return PathBuf::from(path);
+}
+
+
+/// The outcome of checking one part's answer
+#[derive(Debug, PartialEq)]
+pub enum Check {
+    Match { answer: String },
+    Mismatch { expected: String, actual: String },
+    Unknown { actual: String },
+
+    /// The part was asked for but failed, so it has no answer to check
+    Failed { expected: Option<String>, error: String },
+}
+
+
+/// Compare an answer against the expected one, if any
+pub fn check(expected: Option<&String>, actual: String) -> Check {
+    match expected {
+        Some(expected) if *expected == actual => return Check::Match { answer: actual },
+        Some(expected) => return Check::Mismatch { expected: expected.clone(), actual },
+        None => return Check::Unknown { actual },
+    }
+}
+
+
+/// Check the parts of a solution against the expected answers; a part that
+/// wasn't solved is unknown
+pub fn check_solution(solution: &Solution, expected: &Expected) -> [Check; 2] {
+    let check_part = |expected: Option<&String>, part: &Option<Result<Part, Error>>| match part {
+        Some(Ok(part)) => check(expected, part.answer.inline()),
+        Some(Err(e)) => Check::Failed { expected: expected.cloned(), error: e.to_string() },
+        None => Check::Unknown { actual: String::new() },
+    };
+    return [
+        check_part(expected.part1.as_ref(), &solution.part1),
+        check_part(expected.part2.as_ref(), &solution.part2),
+    ];
+}
+
+
+/// Solve a day and check both answers against the expected ones; a part that
+/// fails fails the check, rather than just not matching
+pub fn check_day(day: &Day, source: &str, expected: &Expected) -> Result<[Check; 2], Error> {
+
+    let solution = (day.solve)(source, Parts::Both)?;
+    let checks = check_solution(&solution, expected);
+    match [solution.part1, solution.part2].into_iter().flatten().find_map(|part| part.err()) {
+        Some(e) => return Err(e),
+        None => return Ok(checks),
+    }
+
+}
+
+
+/// Check a day's answers for an input against those kept next to it; errors
+/// point at the input
+pub fn verify(day: &Day, input_path: &Path, source: &str) -> Result<[Check; 2], Error> {
+    let expected = Expected::load(input_path).map_err(|source| Error::Io { path: path_for(input_path), source })?;
+    return check_day(day, source, &expected).map_err(|e| e.in_file(input_path, source));
+}
+
+
+/// The input files in a directory, in order of name; answers files and hidden
+/// files are skipped
+pub fn input_files(dir: &Path) -> Result<Vec<PathBuf>, Error> {
+
+    let io_error = |source| Error::Io { path: dir.to_path_buf(), source };
+    let mut files: Vec<PathBuf> = Vec::new();
+    for entry in fs::read_dir(dir).map_err(io_error)? {
+        let path = entry.map_err(io_error)?.path();
+        let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        if path.is_file() && !name.starts_with('.') && !name.ends_with(SUFFIX) {
+            files.push(path);
+        }
+    }
+    files.sort();
+    return Ok(files);
+
+}
+
+
+/// One input file of a batch: its solution, and how each part's answer
+/// compares with the expected one if answers are checked
+pub struct BatchFile {
+    pub path: PathBuf,
+    pub result: Result<Solution, Error>,
+    pub checks: Option<[Check; 2]>,
+}
+
+
+impl BatchFile {
+
+    /// Whether the input couldn't be solved, or one of its parts failed
+    pub fn failed(&self) -> bool {
+        return self.result.as_ref().map_or(true, |solution| solution.error().is_some());
+    }
+
+    /// How many of its answers don't match the expected ones
+    pub fn mismatches(&self) -> usize {
+        return self.checks.iter().flatten().filter(|c| matches!(c, Check::Mismatch { .. })).count();
+    }
+
+}
+
+
+/// Solve a day against one input file of a batch and, if asked, check the
+/// answers against the answers file next to it. An input without an answers
+/// file has unknown answers.
+pub fn solve_file(day: &Day, input_path: &Path, parts: Parts, check: bool) -> BatchFile {
+
+    let path = input_path.to_path_buf();
+    let expected = match Expected::load(input_path) {
+        Err(e) if check && e.kind() != io::ErrorKind::NotFound => {
+            return BatchFile { path, result: Err(Error::Io { path: path_for(input_path), source: e }), checks: None };
+        }
+        expected => expected.unwrap_or_default(),
+    };
+
+    let result = fs::read_to_string(input_path)
+        .map_err(|source| Error::Io { path: input_path.to_path_buf(), source })
+        .and_then(|source| (day.solve)(&source, parts).map_err(|e| e.in_file(input_path, &source)));
+    let checks = result.as_ref().ok().filter(|_| check).map(|solution| check_solution(solution, &expected));
+    return BatchFile { path, result, checks };
+
+}
+
+
+/// Solve a day against every input file in a directory, in order of name,
+/// checking the answers if asked
+pub fn solve_dir(day: &Day, dir: &Path, parts: Parts, check: bool) -> Result<Vec<BatchFile>, Error> {
+
+    let files = input_files(dir)?;
+    if files.is_empty() {
+        return Err(Error::Usage(format!("No input files in {}", dir.display())));
+    }
+    return Ok(files.iter().map(|path| solve_file(day, path, parts, check)).collect());
+
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let expected = Expected::parse("# day 1\npart1 1655\n\npart2  1683  \n").unwrap();
+        assert_eq!(expected.part1.as_deref(), Some("1655"));
+        assert_eq!(expected.part2.as_deref(), Some("1683"));
+        assert_eq!(Expected::parse("part2 7").unwrap().part1, None);
+        assert!(Expected::parse("part3 7").is_err());
+        assert!(Expected::parse("part1").is_err());
+    }
+
+    #[test]
+    fn test_path_for() {
+        assert_eq!(path_for(Path::new("inputs/day01/part1")), PathBuf::from("inputs/day01/part1.answers"));
+    }
+
+    #[test]
+    fn test_check() {
+        let seven = String::from("7");
+        assert_eq!(check(Some(&seven), String::from("7")), Check::Match { answer: String::from("7") });
+        assert_eq!(
+            check(Some(&seven), String::from("8")),
+            Check::Mismatch { expected: String::from("7"), actual: String::from("8") },
+        );
+        assert_eq!(check(None, String::from("8")), Check::Unknown { actual: String::from("8") });
+    }
+
+    #[test]
+    fn test_solve_file() {
+        let day = super::super::registry::find(1).unwrap();
+        let dir = Path::new("inputs/day01");
+        assert_eq!(input_files(dir).unwrap(), vec![dir.join("part1")]);
+
+        let file = solve_file(day, &dir.join("part1"), Parts::Part1, true);
+        assert!(!file.failed());
+        let [check1, check2] = file.checks.unwrap();
+        assert!(matches!(check1, Check::Match { .. }));
+        assert_eq!(check2, Check::Unknown { actual: String::new() });
+        assert!(solve_file(day, &dir.join("part1"), Parts::Both, false).checks.is_none());
+
+        let file = solve_file(day, &dir.join("missing"), Parts::Both, true);
+        assert!(matches!(file.result, Err(Error::Io { .. })));
+        assert!(file.failed() && file.checks.is_none());
+
+        let batch = solve_dir(day, dir, Parts::Both, true).unwrap();
+        assert_eq!(batch.iter().map(|f| (f.failed(), f.mismatches())).collect::<Vec<_>>(), vec![(false, 0)]);
+        assert!(matches!(solve_dir(day, Path::new("src/bin-missing"), Parts::Both, true), Err(Error::Io { .. })));
+    }
+
+    #[test]
+    fn test_check_day() {
+        let day = super::super::registry::find(1).unwrap();
+        let expected = Expected { part1: Some(String::from("1")), part2: Some(String::from("0")) };
+        assert!(matches!(check_day(day, "1\n2", &expected), Err(Error::Puzzle(_))));
+        let [check1, check2] = check_day(day, "1\n2\n3\n4", &expected).unwrap();
+        assert_eq!(check1, Check::Mismatch { expected: String::from("1"), actual: String::from("3") });
+        assert_eq!(check2, Check::Mismatch { expected: String::from("0"), actual: String::from("1") });
+    }
+
+}
@@ -0,0 +1,265 @@
+//! Repeatedly time each phase of a puzzle and compare against a baseline
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::hint::black_box;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use super::error::{Error, Result};
+use super::puzzle::Puzzle;
+use super::runner;
+
+
+/// Where benchmark results are kept between runs unless told otherwise
+pub const DEFAULT_BASELINE: &str = "target/aoc-bench/baseline.tsv";
+
+
+/// The names of the phases that are timed, in the order they run
+pub const PHASES: [&str; 3] = ["parse", "part1", "part2"];
+
+
+/// How many times to run each phase
+pub struct Config {
+    pub warmup: u32,
+    pub iterations: u32,
+}
+
+
+impl Default for Config {
+    fn default() -> Config {
+        return Config { warmup: 10, iterations: 100 };
+    }
+}
+
+
+/// Summary statistics over the samples taken for one phase
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+
+impl Stats {
+
+    /// Summarize a non-empty set of samples
+    pub fn from_samples(samples: &mut [Duration]) -> Stats {
+
+        samples.sort();
+        let n = samples.len();
+        let nanos: Vec<f64> = samples.iter().map(|s| s.as_nanos() as f64).collect();
+
+        let median = if n % 2 == 1 {
+            nanos[n / 2]
+        } else {
+            (nanos[n / 2 - 1] + nanos[n / 2]) / 2.0
+        };
+        let mean = nanos.iter().sum::<f64>() / n as f64;
+        let variance = nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n as f64;
+
+        return Stats {
+            min: samples[0],
+            median: Duration::from_nanos(median as u64),
+            mean: Duration::from_nanos(mean as u64),
+            stddev: Duration::from_nanos(variance.sqrt() as u64),
+        };
+
+    }
+
+}
+
+
+/// Statistics for every phase of a puzzle, in the order of PHASES
+pub struct Bench {
+    pub phases: [Stats; 3],
+}
+
+
+/// Time a closure `config.iterations` times after `config.warmup` untimed runs
+fn sample<T>(config: &Config, mut f: impl FnMut() -> T) -> Stats {
+
+    for _ in 0..config.warmup {
+        black_box(f());
+    }
+
+    let mut samples: Vec<Duration> = Vec::with_capacity(config.iterations as usize);
+    for _ in 0..config.iterations {
+        let start = Instant::now();
+        black_box(f());
+        samples.push(start.elapsed());
+    }
+
+    return Stats::from_samples(&mut samples);
+
+}
+
+
+/// Benchmark the parse, part 1 and part 2 phases of a puzzle. Each phase is
+/// run once up front so that errors are reported instead of timed.
+pub fn bench<P: Puzzle>(source: &str, config: &Config) -> Result<Bench> {
+
+    if config.iterations == 0 {
+        return Err(Error::Usage(String::from("a benchmark needs at least one iteration")));
+    }
+
+    return runner::isolate(|| {
+        let input = P::parse(source)?;
+        P::part1(&input)?;
+        P::part2(&input)?;
+
+        let parse = sample(config, || P::parse(black_box(source)));
+        let part1 = sample(config, || P::part1(black_box(&input)));
+        let part2 = sample(config, || P::part2(black_box(&input)));
+
+        return Ok(Bench { phases: [parse, part1, part2] });
+    });
+
+}
+
+
+/// Stored benchmark results, keyed by day and phase
+#[derive(Debug, Default, PartialEq)]
+pub struct Baseline {
+    pub entries: BTreeMap<(u32, String), Stats>,
+}
+
+
+impl Baseline {
+
+    /// Load a baseline; a missing file is an empty baseline
+    pub fn load(path: &Path) -> Result<Baseline> {
+
+        let source = match fs::read_to_string(path) {
+            Ok(source) => source,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Baseline::default()),
+            Err(source) => return Err(Error::Io { path: path.to_path_buf(), source }),
+        };
+        return Baseline::parse(&source);
+
+    }
+
+    /// Parse the tab separated format written by `Baseline::to_tsv`
+    pub fn parse(source: &str) -> Result<Baseline> {
+
+        let mut baseline = Baseline::default();
+        for (line_idx, line) in source.lines().enumerate() {
+            if line.starts_with('#') || line.trim().is_empty() {
+                continue;
+            }
+            let fields: Vec<&str> = line.split('\t').collect();
+            let bad_line = || Error::parse(line_idx + 1, 1, "malformed benchmark baseline");
+            if fields.len() != 6 {
+                return Err(bad_line());
+            }
+            let day: u32 = fields[0].parse().map_err(|_| bad_line())?;
+            let mut nanos = [Duration::ZERO; 4];
+            for idx in 0..4 {
+                nanos[idx] = Duration::from_nanos(fields[idx + 2].parse().map_err(|_| bad_line())?);
+            }
+            let stats = Stats { min: nanos[0], median: nanos[1], mean: nanos[2], stddev: nanos[3] };
+            baseline.entries.insert((day, fields[1].to_string()), stats);
+        }
+        return Ok(baseline);
+
+    }
+
+    /// Record the results of benchmarking a day, replacing older results
+    pub fn record(&mut self, day: u32, bench: &Bench) {
+        for (phase, stats) in PHASES.iter().zip(bench.phases.iter()) {
+            self.entries.insert((day, phase.to_string()), *stats);
+        }
+    }
+
+    pub fn get(&self, day: u32, phase: &str) -> Option<&Stats> {
+        return self.entries.get(&(day, phase.to_string()));
+    }
+
+    /// Write the baseline as tab separated values, one phase per line
+    pub fn to_tsv(&self) -> String {
+        let mut output = String::from("# day\tphase\tmin_ns\tmedian_ns\tmean_ns\tstddev_ns\n");
+        for ((day, phase), stats) in self.entries.iter() {
+            output.push_str(&format!(
+                "{}\t{}\t{}\t{}\t{}\t{}\n",
+                day, phase,
+                stats.min.as_nanos(), stats.median.as_nanos(),
+                stats.mean.as_nanos(), stats.stddev.as_nanos(),
+            ));
+        }
+        return output;
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        return fs::write(path, self.to_tsv());
+    }
+
+}
+
+
+/// Relative change in median time from a baseline, e.g. 0.25 for 25% slower
+pub fn change(baseline: &Stats, current: &Stats) -> f64 {
+    let before = baseline.median.as_nanos().max(1) as f64;
+    let after = current.median.as_nanos() as f64;
+    return (after - before) / before;
+}
+
+
+/// Whether the current median is slower than the baseline by more than the
+/// threshold (a fraction, e.g. 0.1 for 10%)
+pub fn is_regression(baseline: &Stats, current: &Stats, threshold: f64) -> bool {
+    return change(baseline, current) > threshold;
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ns(n: u64) -> Duration {
+        return Duration::from_nanos(n);
+    }
+
+    #[test]
+    fn test_stats() {
+        let mut samples = vec![ns(4), ns(2), ns(9), ns(5)];
+        let stats = Stats::from_samples(&mut samples);
+        assert_eq!(stats.min, ns(2));
+        assert_eq!(stats.median, ns(4));  // (4 + 5) / 2, truncated
+        assert_eq!(stats.mean, ns(5));
+        assert_eq!(stats.stddev, ns(2));  // sqrt(6.5), truncated
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let stats = Stats { min: ns(1), median: ns(2), mean: ns(3), stddev: ns(4) };
+        let mut baseline = Baseline::default();
+        baseline.record(3, &Bench { phases: [stats, stats, stats] });
+        let parsed = Baseline::parse(&baseline.to_tsv()).unwrap();
+        assert_eq!(baseline, parsed);
+        assert_eq!(parsed.get(3, "part2"), Some(&stats));
+        assert!(Baseline::parse("3\tparse\t1\t2").is_err());
+    }
+
+    #[test]
+    fn test_regression() {
+        let before = Stats { min: ns(90), median: ns(100), mean: ns(100), stddev: ns(1) };
+        let slower = Stats { median: ns(125), ..before };
+        let similar = Stats { median: ns(105), ..before };
+        assert!(is_regression(&before, &slower, 0.1));
+        assert!(!is_regression(&before, &similar, 0.1));
+        assert!(!is_regression(&before, &before, 0.0));
+    }
+
+    #[test]
+    fn test_no_iterations() {
+        let config = Config { warmup: 0, iterations: 0 };
+        assert!(matches!(bench::<super::super::day01::Day01>("1\n2\n3", &config), Err(Error::Usage(_))));
+    }
+
+}
@@ -0,0 +1,522 @@
+//! Command line arguments
+
+use std::path::PathBuf;
+
+use aoc_2021_sjunot::bench;
+use aoc_2021_sjunot::runner::Parts;
+use aoc_2021_sjunot::stress;
+
+
+/// Directory holding the dayNN/part1 inputs unless told otherwise
+pub const DEFAULT_INPUTS: &str = "inputs";
+
+
+/// Environment variable overriding DEFAULT_INPUTS
+pub const INPUTS_ENV: &str = "AOC_INPUTS";
+
+
+/// Input path meaning "read from standard input"
+pub const STDIN: &str = "-";
+
+
+pub const USAGE: &str = "\
+Usage:
+    aoc-2021-sjunot <day> [input] [--json]   solve one day against an input file, or every file in a directory
+    aoc-2021-sjunot <day> --example          solve one day against the example in its description
+    aoc-2021-sjunot <day> [input] --stream   solve one day reading its input a line at a time (day 1 only)
+    aoc-2021-sjunot <day> [input] --analyze  describe how the depths and their window sums change (day 1 only)
+    aoc-2021-sjunot watch <day> [input]      solve one day again whenever its input file changes
+    aoc-2021-sjunot all [--json]             solve every completed day against its input
+    aoc-2021-sjunot bench [day] [--json]     time each phase of one or every completed day
+    aoc-2021-sjunot verify [--json]          check every completed day against its known answers
+    aoc-2021-sjunot generate <day>           print a random input for a day
+    aoc-2021-sjunot stress [day]             check one or every completed day against brute force on random inputs
+    aoc-2021-sjunot new <day> [title]        generate the skeleton of a new day (run from the crate root)
+
+Without an input, a day reads <inputs>/dayNN/part1. An input of \"-\" reads from stdin.
+
+Options:
+    --json              print a single JSON document instead of text
+    --inputs <dir>      directory holding the dayNN/part1 inputs (default $AOC_INPUTS, then inputs)
+    --part <1|2>        solve only one part of each day (default both)
+    --check             check the answers for a directory of inputs against their .answers files
+    --example           check a day's answers to the example in its puzzle description
+    --stream            solve in constant memory, for inputs too big to read whole (day 1 only)
+    --analyze           report runs, rises, drops and where the depths increase (day 1 only)
+    --window <n>        depths per sliding window in part 2 (day 1 only, default 3)
+    --warmup <n>        untimed runs of each phase before measuring (bench, default 10)
+    --iterations <n>    timed runs of each phase (bench, default 100)
+    --baseline <path>   stored results to compare against (bench, default target/aoc-bench/baseline.tsv)
+    --threshold <pct>   slowdown of the median that counts as a regression (bench, default 10)
+    --save              store the results as the new baseline (bench)
+    --seed <n>          seed for the random inputs (generate, stress, default 2021)
+    --size <n>          lines, boards, etc. in each random input (generate, stress, default 100)
+    --cases <n>         random inputs to check (stress, default 1000)
+
+Exit status:
+    0 success, 1 some days of a batch failed, 2 usage error, 3 input couldn't be read,
+    4 input couldn't be parsed, 5 input breaks the puzzle's rules, 6 the solver panicked";
+
+
+/// What the binary was asked to do
+#[derive(Debug, PartialEq)]
+pub enum Command {
+
+    /// Solve one day against the given input file (or every file in the
+    /// given directory, optionally checking their answers), or its default
+    /// input. Day 1 can use a window of another size for part 2.
+    Day { day: u32, input: Option<PathBuf>, parts: Parts, check: bool, window: Option<usize> },
+
+    /// Solve one day again whenever its input file changes
+    Watch { day: u32, input: Option<PathBuf>, parts: Parts },
+
+    /// Check a day's answers to the example in its puzzle description
+    Example { day: u32 },
+
+    /// Solve one day reading its input a line at a time rather than whole
+    Stream { day: u32, input: Option<PathBuf>, parts: Parts, window: Option<usize> },
+
+    /// Describe how a day's input changes, beyond the puzzle's answers
+    Analyze { day: u32, input: Option<PathBuf>, window: Option<usize> },
+
+    /// Solve every completed day against its default input
+    All { parts: Parts },
+
+    /// Check every completed day against the answers kept next to its input
+    Verify,
+
+    /// Print a random input for a day
+    Generate { day: u32, seed: u64, size: usize },
+
+    /// Check one day, or every completed day, against brute force on random
+    /// inputs
+    Stress { day: Option<u32>, config: stress::Config },
+
+    /// Generate the module, registration and input directory for a new day
+    New { day: u32, title: Option<String> },
+
+    /// Benchmark one day, or every completed day, against the default input
+    Bench {
+        day: Option<u32>,
+        warmup: u32,
+        iterations: u32,
+        baseline: PathBuf,
+        threshold: f64,
+        save: bool,
+    },
+
+}
+
+
+/// How results are presented
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+
+/// Everything parsed from the command line
+#[derive(Debug, PartialEq)]
+pub struct Args {
+    pub command: Command,
+    pub format: Format,
+    pub inputs: Option<PathBuf>,
+}
+
+
+/// Parse a day argument, e.g. "1"
+fn parse_day(day: &str) -> Result<u32, String> {
+    return day.parse().map_err(
+        |_| format!("'day' argument must be a positive integer (e.g. '1'), got '{}'", day)
+    );
+}
+
+
+/// Parse the value given to --part
+fn parse_part(value: Option<&String>) -> Result<Parts, String> {
+    match value.map(|v| v.as_str()) {
+        Some("1") => return Ok(Parts::Part1),
+        Some("2") => return Ok(Parts::Part2),
+        Some(other) => return Err(format!("Invalid value for '--part': '{}' (expected 1 or 2)", other)),
+        None => return Err(String::from("Option '--part' requires a value")),
+    }
+}
+
+
+/// Parse the value given to an option, e.g. the "5" in "--warmup 5"
+fn parse_value<T: std::str::FromStr>(option: &str, value: Option<&String>) -> Result<T, String> {
+    let value = value.ok_or(format!("Option '{}' requires a value", option))?;
+    return value.parse().map_err(|_| format!("Invalid value for '{}': '{}'", option, value));
+}
+
+
+/// Parse the arguments given to the binary (excluding the program name)
+pub fn parse(args: &[String]) -> Result<Args, String> {
+
+    // Separate options from positional arguments
+    let mut format = Format::Text;
+    let mut inputs: Option<PathBuf> = None;
+    let mut parts: Option<Parts> = None;
+    let mut warmup: Option<u32> = None;
+    let mut iterations: Option<u32> = None;
+    let mut baseline: Option<PathBuf> = None;
+    let mut threshold: Option<f64> = None;
+    let mut save = false;
+    let mut example = false;
+    let mut stream = false;
+    let mut analyze = false;
+    let mut check = false;
+    let mut seed: Option<u64> = None;
+    let mut size: Option<usize> = None;
+    let mut cases: Option<u32> = None;
+    let mut window: Option<usize> = None;
+    let mut positional: Vec<&String> = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => format = Format::Json,
+            "--save" => save = true,
+            "--example" => example = true,
+            "--stream" => stream = true,
+            "--analyze" => analyze = true,
+            "--check" => check = true,
+            "--inputs" => inputs = Some(parse_value(arg, args.next())?),
+            "--part" => parts = Some(parse_part(args.next())?),
+            "--warmup" => warmup = Some(parse_value(arg, args.next())?),
+            "--iterations" => match parse_value(arg, args.next())? {
+                0 => return Err(String::from("Option '--iterations' must be at least 1")),
+                n => iterations = Some(n),
+            },
+            "--baseline" => baseline = Some(parse_value(arg, args.next())?),
+            "--threshold" => threshold = Some(parse_value(arg, args.next())?),
+            "--seed" => seed = Some(parse_value(arg, args.next())?),
+            "--size" => size = Some(parse_value(arg, args.next())?),
+            "--cases" => cases = Some(parse_value(arg, args.next())?),
+            "--window" => window = Some(parse_value(arg, args.next())?),
+            flag if flag.starts_with("--") => return Err(format!("Unknown option '{}'", flag)),
+            _ => positional.push(arg),
+        }
+    }
+
+    let is_bench = positional.first().is_some_and(|p| *p == "bench");
+    if !is_bench && (save || warmup.is_some() || iterations.is_some() || baseline.is_some() || threshold.is_some()) {
+        return Err(String::from("Benchmark options are only valid with 'bench'"));
+    }
+    let is_stress = positional.first().is_some_and(|p| *p == "stress");
+    let is_generate = positional.first().is_some_and(|p| *p == "generate");
+    if !is_stress && cases.is_some() {
+        return Err(String::from("'--cases' is only valid with 'stress'"));
+    }
+    if !is_stress && !is_generate && (seed.is_some() || size.is_some()) {
+        return Err(String::from("'--seed' and '--size' are only valid with 'generate' or 'stress'"));
+    }
+    if format == Format::Json && positional.first().is_some_and(|p| *p == "watch") {
+        return Err(String::from("'--json' isn't supported with 'watch'"));
+    }
+    if parts.is_some() && !positional.first().is_some_and(|p| *p == "all" || *p == "watch" || p.parse::<u32>().is_ok()) {
+        return Err(String::from("'--part' is only valid when solving a day or all days"));
+    }
+    let solves_day = positional.first().is_some_and(|p| p.parse::<u32>().is_ok());
+    if window.is_some() && (example || check || !solves_day) {
+        return Err(String::from("'--window' is only valid when solving day 1 against a single input"));
+    }
+    if window == Some(0) {
+        return Err(String::from("'--window' must be at least 1"));
+    }
+    if example && (stream || analyze) {
+        return Err(String::from("'--example' can't be used with '--stream' or '--analyze'"));
+    }
+    if example && parts.is_some() {
+        return Err(String::from("'--example' checks both parts and can't be used with '--part'"));
+    }
+    if analyze && parts.is_some() {
+        return Err(String::from("'--analyze' covers both parts and can't be used with '--part'"));
+    }
+    let parts = parts.unwrap_or(Parts::Both);
+
+    if example {
+        return match positional.as_slice() {
+            [day] => Ok(Args { command: Command::Example { day: parse_day(day)? }, format, inputs }),
+            _ => Err(String::from("'--example' takes a single day and no input")),
+        };
+    }
+
+    if analyze {
+        if stream || check {
+            return Err(String::from("'--analyze' reads a single input and can't be used with '--stream' or '--check'"));
+        }
+        return match positional.as_slice() {
+            [day, input @ ..] if input.len() <= 1 => Ok(Args {
+                command: Command::Analyze { day: parse_day(day)?, input: input.first().map(PathBuf::from), window },
+                format,
+                inputs,
+            }),
+            _ => Err(String::from("'--analyze' takes a day and an optional input")),
+        };
+    }
+
+    if stream {
+        if check {
+            return Err(String::from("'--stream' reads a single input and can't be used with '--check'"));
+        }
+        return match positional.as_slice() {
+            [day, input @ ..] if input.len() <= 1 => Ok(Args {
+                command: Command::Stream { day: parse_day(day)?, input: input.first().map(PathBuf::from), parts, window },
+                format,
+                inputs,
+            }),
+            _ => Err(String::from("'--stream' takes a day and an optional input")),
+        };
+    }
+
+    if check && !(positional.len() == 2 && positional[0].parse::<u32>().is_ok()) {
+        return Err(String::from("'--check' needs a day and a directory of inputs"));
+    }
+
+    let command = match positional.as_slice() {
+        [all] if *all == "all" => Command::All { parts },
+        [verify] if *verify == "verify" => Command::Verify,
+        [bench, day @ ..] if *bench == "bench" && day.len() <= 1 => {
+            let defaults = bench::Config::default();
+            Command::Bench {
+                day: day.first().map(|d| parse_day(d)).transpose()?,
+                warmup: warmup.unwrap_or(defaults.warmup),
+                iterations: iterations.unwrap_or(defaults.iterations),
+                baseline: baseline.unwrap_or(PathBuf::from(bench::DEFAULT_BASELINE)),
+                threshold: threshold.unwrap_or(10.0) / 100.0,
+                save,
+            }
+        }
+        [watch, day, input @ ..] if *watch == "watch" && input.len() <= 1 => Command::Watch {
+            day: parse_day(day)?,
+            input: input.first().map(PathBuf::from),
+            parts,
+        },
+        [generate, day] if *generate == "generate" => {
+            let defaults = stress::Config::default();
+            Command::Generate { day: parse_day(day)?, seed: seed.unwrap_or(defaults.seed), size: size.unwrap_or(defaults.size) }
+        }
+        [stress, day @ ..] if *stress == "stress" && day.len() <= 1 => {
+            let defaults = stress::Config::default();
+            Command::Stress {
+                day: day.first().map(|d| parse_day(d)).transpose()?,
+                config: stress::Config {
+                    seed: seed.unwrap_or(defaults.seed),
+                    cases: cases.unwrap_or(defaults.cases),
+                    size: size.unwrap_or(defaults.size),
+                },
+            }
+        }
+        [new, day, title @ ..] if *new == "new" && title.len() <= 1 => Command::New {
+            day: parse_day(day)?,
+            title: title.first().map(|t| t.to_string()),
+        },
+        [day] => Command::Day { day: parse_day(day)?, input: None, parts, check, window },
+        [day, input] => Command::Day { day: parse_day(day)?, input: Some(PathBuf::from(input)), parts, check, window },
+        _ => return Err(String::from("Invalid number of arguments")),
+    };
+
+    return Ok(Args { command, format, inputs });
+
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        return s.split_whitespace().map(String::from).collect();
+    }
+
+    #[test]
+    fn test_parse_day() {
+        assert_eq!(
+            parse(&args("4 inputs/day04/part1")).map(|a| a.command),
+            Ok(Command::Day { day: 4, input: Some(PathBuf::from("inputs/day04/part1")), parts: Parts::Both, check: false, window: None }),
+        );
+        assert_eq!(parse(&args("4")).map(|a| a.command), Ok(Command::Day { day: 4, input: None, parts: Parts::Both, check: false, window: None }));
+        assert_eq!(
+            parse(&args("4 -")).map(|a| a.command),
+            Ok(Command::Day { day: 4, input: Some(PathBuf::from(STDIN)), parts: Parts::Both, check: false, window: None }),
+        );
+        assert!(parse(&args("four inputs/day04/part1")).is_err());
+    }
+
+    #[test]
+    fn test_parse_all() {
+        assert_eq!(parse(&args("all")).map(|a| a.command), Ok(Command::All { parts: Parts::Both }));
+        assert!(parse(&args("")).is_err());
+        assert!(parse(&args("all extra things")).is_err());
+    }
+
+    #[test]
+    fn test_parse_verify() {
+        assert_eq!(parse(&args("verify --json")).map(|a| a.command), Ok(Command::Verify));
+        assert!(parse(&args("verify 1")).is_err());
+    }
+
+    #[test]
+    fn test_parse_part() {
+        assert_eq!(
+            parse(&args("3 --part 2")).map(|a| a.command),
+            Ok(Command::Day { day: 3, input: None, parts: Parts::Part2, check: false, window: None }),
+        );
+        assert_eq!(parse(&args("--part 1 all")).map(|a| a.command), Ok(Command::All { parts: Parts::Part1 }));
+        assert!(parse(&args("3 --part 3")).is_err());
+        assert!(parse(&args("3 --part")).is_err());
+        assert!(parse(&args("verify --part 1")).is_err());
+    }
+
+    #[test]
+    fn test_parse_example() {
+        assert_eq!(parse(&args("4 --example")).map(|a| a.command), Ok(Command::Example { day: 4 }));
+        assert_eq!(parse(&args("--example 4 --json")).map(|a| a.format), Ok(Format::Json));
+        assert!(parse(&args("4 inputs/day04/part1 --example")).is_err());
+        assert!(parse(&args("all --example")).is_err());
+        assert!(parse(&args("4 --example --part 1")).is_err());
+    }
+
+    #[test]
+    fn test_parse_stream() {
+        assert_eq!(
+            parse(&args("1 huge.txt --stream --part 2")).map(|a| a.command),
+            Ok(Command::Stream { day: 1, input: Some(PathBuf::from("huge.txt")), parts: Parts::Part2, window: None }),
+        );
+        assert_eq!(parse(&args("--stream 1")).map(|a| a.command), Ok(Command::Stream { day: 1, input: None, parts: Parts::Both, window: None }));
+        assert!(parse(&args("all --stream")).is_err());
+        assert!(parse(&args("watch 1 --stream")).is_err());
+        assert!(parse(&args("1 --stream --example")).is_err());
+        assert!(parse(&args("1 dir --stream --check")).is_err());
+    }
+
+    #[test]
+    fn test_parse_analyze() {
+        assert_eq!(
+            parse(&args("1 --analyze --window 4")).map(|a| a.command),
+            Ok(Command::Analyze { day: 1, input: None, window: Some(4) }),
+        );
+        assert_eq!(
+            parse(&args("1 depths.txt --analyze --json")).map(|a| a.command),
+            Ok(Command::Analyze { day: 1, input: Some(PathBuf::from("depths.txt")), window: None }),
+        );
+        assert!(parse(&args("1 --analyze --part 1")).is_err());
+        assert!(parse(&args("1 --analyze --stream")).is_err());
+        assert!(parse(&args("1 --analyze --example")).is_err());
+        assert!(parse(&args("all --analyze")).is_err());
+    }
+
+    #[test]
+    fn test_parse_window() {
+        assert_eq!(
+            parse(&args("1 --window 5")).map(|a| a.command),
+            Ok(Command::Day { day: 1, input: None, parts: Parts::Both, check: false, window: Some(5) }),
+        );
+        assert_eq!(
+            parse(&args("1 huge.txt --stream --window 2")).map(|a| a.command),
+            Ok(Command::Stream { day: 1, input: Some(PathBuf::from("huge.txt")), parts: Parts::Both, window: Some(2) }),
+        );
+        assert!(parse(&args("1 --window 0")).is_err());
+        assert!(parse(&args("1 --window")).is_err());
+        assert!(parse(&args("1 --window 5 --example")).is_err());
+        assert!(parse(&args("1 everyone --window 5 --check")).is_err());
+        assert!(parse(&args("all --window 5")).is_err());
+    }
+
+    #[test]
+    fn test_parse_check() {
+        assert_eq!(
+            parse(&args("4 everyone --check")).map(|a| a.command),
+            Ok(Command::Day { day: 4, input: Some(PathBuf::from("everyone")), parts: Parts::Both, check: true, window: None }),
+        );
+        assert!(parse(&args("4 --check")).is_err());
+        assert!(parse(&args("all --check")).is_err());
+        assert!(parse(&args("watch 4 everyone --check")).is_err());
+    }
+
+    #[test]
+    fn test_parse_watch() {
+        assert_eq!(
+            parse(&args("watch 4 --part 2")).map(|a| a.command),
+            Ok(Command::Watch { day: 4, input: None, parts: Parts::Part2 }),
+        );
+        assert_eq!(
+            parse(&args("watch 4 mine.txt")).map(|a| a.command),
+            Ok(Command::Watch { day: 4, input: Some(PathBuf::from("mine.txt")), parts: Parts::Both }),
+        );
+        assert!(parse(&args("watch")).is_err());
+        assert!(parse(&args("watch 4 --json")).is_err());
+    }
+
+    #[test]
+    fn test_parse_stress() {
+        assert_eq!(
+            parse(&args("stress 3 --cases 50 --seed 9")).map(|a| a.command),
+            Ok(Command::Stress { day: Some(3), config: stress::Config { seed: 9, cases: 50, size: 100 } }),
+        );
+        assert!(matches!(parse(&args("stress")).map(|a| a.command), Ok(Command::Stress { day: None, .. })));
+        assert_eq!(
+            parse(&args("generate 4 --size 3")).map(|a| a.command),
+            Ok(Command::Generate { day: 4, seed: 2021, size: 3 }),
+        );
+        assert!(parse(&args("generate")).is_err());
+        assert!(parse(&args("generate 4 --cases 3")).is_err());
+        assert!(parse(&args("4 --seed 3")).is_err());
+        assert!(parse(&args("stress --part 1")).is_err());
+    }
+
+    #[test]
+    fn test_parse_new() {
+        assert_eq!(parse(&args("new 5")).map(|a| a.command), Ok(Command::New { day: 5, title: None }));
+        assert_eq!(
+            parse(&[String::from("new"), String::from("5"), String::from("Hydrothermal Venture")]).map(|a| a.command),
+            Ok(Command::New { day: 5, title: Some(String::from("Hydrothermal Venture")) }),
+        );
+        assert!(parse(&args("new")).is_err());
+        assert!(parse(&args("new five")).is_err());
+        assert!(parse(&args("new 5 --part 1")).is_err());
+    }
+
+    #[test]
+    fn test_parse_inputs() {
+        assert_eq!(parse(&args("all")).map(|a| a.inputs), Ok(None));
+        assert_eq!(
+            parse(&args("2 --inputs elsewhere")).map(|a| a.inputs),
+            Ok(Some(PathBuf::from("elsewhere"))),
+        );
+        assert!(parse(&args("2 --inputs")).is_err());
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!(parse(&args("all")).map(|a| a.format), Ok(Format::Text));
+        assert_eq!(parse(&args("--json all")).map(|a| a.format), Ok(Format::Json));
+        assert_eq!(parse(&args("1 input --json")).map(|a| a.format), Ok(Format::Json));
+        assert!(parse(&args("all --yaml")).is_err());
+    }
+
+    #[test]
+    fn test_parse_bench() {
+        assert_eq!(
+            parse(&args("bench 3 --iterations 7 --threshold 25 --save")).map(|a| a.command),
+            Ok(Command::Bench {
+                day: Some(3),
+                warmup: 10,
+                iterations: 7,
+                baseline: PathBuf::from(bench::DEFAULT_BASELINE),
+                threshold: 0.25,
+                save: true,
+            }),
+        );
+        assert!(matches!(
+            parse(&args("bench")).map(|a| a.command),
+            Ok(Command::Bench { day: None, .. }),
+        ));
+        assert!(parse(&args("bench --warmup")).is_err());
+        assert!(parse(&args("bench --warmup lots")).is_err());
+        assert!(parse(&args("bench --iterations 0")).is_err());
+        assert!(parse(&args("all --save")).is_err());
+    }
+
+}
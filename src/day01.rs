@@ -1,107 +1,505 @@
-// AoC 2021 - Day 1
+//! AoC 2021 - Day 1: Sonar Sweep
+//!
+//! Count how often a series of depth measurements increases, first reading by
+//! reading and then over a sliding window of three readings (or any other
+//! size, with [`window_increases`]). Inputs too big to keep in memory can be
+//! counted a line at a time with [`sweep`], and [`analyze`] describes the
+//! changes in more detail than a count.
 
-use std::fs;
+use std::io::BufRead;
 use std::path::Path;
-use std::process;
+use std::time::{Duration, Instant};
 
+use super::answer::Answer;
+use super::error::{Error, Result};
+use super::puzzle::{Example, Puzzle};
+use super::runner::{self, Part, Parts, Solution};
+use super::stress::Stress;
+use super::utils::{number_on_line, numbers_per_line, Rng};
 
-fn part1(input: &Vec<i32>) -> i32 {
 
-    let mut r: i32 = 0;
-    let mut x: i32 = input[0];
+/// Count the measurements that are larger than the previous measurement
+pub fn part1<T: PartialOrd>(input: &[T]) -> Result<usize> {
+
+    if input.is_empty() {
+        return Err(Error::puzzle("no depth measurements were given"));
+    }
+
+    let mut r: usize = 0;
+    let mut x: &T = &input[0];
     for i in input.iter().skip(1) {
-        if i > &x {
+        if i > x {
             r += 1;
         }
-        x = *i;
+        x = i;
     }
-    return r;
+    return Ok(r);
 
 }
 
-fn slide_sum_over(input: &Vec<i32>) -> Vec<i32> {
+/// The size of the sliding window in part 2
+pub const WINDOW: usize = 3;
+
+
+/// Check that windows of the given size can be taken from `count` depths
+fn check_window(count: usize, window: usize) -> Result<()> {
 
-    if input.len() < 3 {
-        eprintln!("minimum of three inputs are required for part 2");
-        process::exit(1);
+    if window == 0 {
+        return Err(Error::puzzle("the window must hold at least one measurement"));
     }
+    if count < window {
+        return Err(Error::puzzle(format!(
+            "minimum of {} inputs are required for a window of {}, got {}", window, window, count,
+        )));
+    }
+    return Ok(());
+
+}
+
+/// Sum each window of `window` consecutive measurements, widened so that no
+/// sum can overflow
+///
+/// ```
+/// use aoc_2021_sjunot::day01::slide_sum_over;
+///
+/// assert_eq!(slide_sum_over(&[199, 200, 208, 210], 3).unwrap(), vec![607, 618]);
+/// assert_eq!(slide_sum_over(&[199, 200, 208, 210], 2).unwrap(), vec![399, 408, 418]);
+/// ```
+pub fn slide_sum_over(input: &[i32], window: usize) -> Result<Vec<i64>> {
+
+    check_window(input.len(), window)?;
 
-    let num_sums: usize = input.len() - 2;
-    let mut output: Vec<i32> = Vec::with_capacity(num_sums);
-    for i in 0..num_sums {
-        let sum = input[i] + input[i + 1] + input[i + 2];
+    let mut sum: i64 = input[..window].iter().map(|&d| i64::from(d)).sum();
+    let mut output: Vec<i64> = Vec::with_capacity(input.len() - window + 1);
+    output.push(sum);
+    for i in window..input.len() {
+        sum += i64::from(input[i]) - i64::from(input[i - window]);
         output.push(sum);
     }
 
-    return output;
+    return Ok(output);
+
+}
+
+/// Count the sums of `window`-measurement windows that are larger than the
+/// previous sum, without summing: consecutive windows share all but their
+/// first and last measurements, so one sum is larger exactly when its last
+/// measurement is larger than the first measurement of the other
+///
+/// ```
+/// use aoc_2021_sjunot::day01::window_increases;
+///
+/// let depths = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+/// assert_eq!(window_increases(&depths, 3).unwrap(), 5);
+/// assert_eq!(window_increases(&depths, 1).unwrap(), 7);
+/// assert!(window_increases(&depths[..2], 3).is_err());
+/// ```
+pub fn window_increases(input: &[i32], window: usize) -> Result<usize> {
+
+    check_window(input.len(), window)?;
+    return Ok((window..input.len()).filter(|&i| input[i] > input[i - window]).count());
+
+}
+
+/// Count the sums of three-measurement windows that are larger than the
+/// previous sum
+pub fn part2(input: &[i32]) -> Result<usize> {
+    return window_increases(input, WINDOW);
+}
+
+/// A run of strictly increasing values, by the indices of its first and last
+/// values
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Run {
+    pub start: usize,
+    pub end: usize,
+}
+
+
+impl Run {
+
+    /// How many values the run holds
+    pub fn length(&self) -> usize {
+        return self.end - self.start + 1;
+    }
+
+}
+
+
+/// The change from one value to the next, at the index of the second
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub index: usize,
+    pub change: i64,
+}
+
+
+/// Everything about how a series of values changes: depths, or the sums of
+/// their windows
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Analysis {
+    pub values: usize,
+
+    /// Changes from one value to the next, by direction
+    pub increases: usize,
+    pub decreases: usize,
+    pub unchanged: usize,
+
+    /// The longest run of strictly increasing values (the first if there are
+    /// several); a single value counts as a run
+    pub longest_run: Run,
+
+    /// The largest single fall and rise, if there are any
+    pub largest_drop: Option<Step>,
+    pub largest_rise: Option<Step>,
+
+    /// Indices of the values larger than the value before them
+    pub increase_positions: Vec<usize>,
+}
+
+
+/// Analyze how a series of values changes; `part1` counts its increases. There
+/// must be at least one value, and every change must fit in an i64.
+///
+/// ```
+/// use aoc_2021_sjunot::day01::{analyze, slide_sum_over};
+///
+/// let depths = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+/// let analysis = analyze(&depths).unwrap();
+/// assert_eq!(analysis.increases, 7);
+/// assert_eq!((analysis.longest_run.start, analysis.longest_run.end), (0, 3));
+///
+/// let windows = analyze(&slide_sum_over(&depths, 3).unwrap()).unwrap();
+/// assert_eq!(windows.increase_positions, vec![1, 4, 5, 6, 7]);
+/// ```
+pub fn analyze<T: Copy + PartialOrd + Into<i64>>(input: &[T]) -> Result<Analysis> {
+
+    let increases = part1(input)?;
+
+    let mut analysis = Analysis {
+        values: input.len(),
+        increases,
+        decreases: 0,
+        unchanged: 0,
+        longest_run: Run { start: 0, end: 0 },
+        largest_drop: None,
+        largest_rise: None,
+        increase_positions: Vec::with_capacity(increases),
+    };
+
+    let mut run_start = 0;
+    for index in 1..input.len() {
+        let change = input[index].into().checked_sub(input[index - 1].into()).ok_or_else(
+            || Error::puzzle(format!("the change at index {} overflows", index))
+        )?;
+        let step = Step { index, change };
+        if change > 0 {
+            analysis.increase_positions.push(index);
+            if analysis.largest_rise.is_none_or(|rise| change > rise.change) {
+                analysis.largest_rise = Some(step);
+            }
+            if index - run_start + 1 > analysis.longest_run.length() {
+                analysis.longest_run = Run { start: run_start, end: index };
+            }
+        } else {
+            run_start = index;
+            if change < 0 {
+                analysis.decreases += 1;
+                if analysis.largest_drop.is_none_or(|drop| change < drop.change) {
+                    analysis.largest_drop = Some(step);
+                }
+            } else {
+                analysis.unchanged += 1;
+            }
+        }
+    }
+
+    return Ok(analysis);
+
+}
+
+/// How the depths in an input change, and how the sums of their windows do
+#[derive(Debug)]
+pub struct Analyses {
+    pub window: usize,
+    pub depths: Analysis,
+
+    /// The windows might not be analyzable even when the depths are, e.g. if
+    /// there are fewer depths than a window holds
+    pub windows: Result<Analysis>,
+}
+
+
+/// Parse the depths in an input and analyze them and the sums of their windows
+/// of the given size
+///
+/// ```
+/// use aoc_2021_sjunot::day01::analyze_input;
+///
+/// let analyses = analyze_input("199\n200\n208", 4).unwrap();
+/// assert_eq!(analyses.depths.increases, 2);
+/// assert!(analyses.windows.is_err());
+/// ```
+pub fn analyze_input(source: &str, window: usize) -> Result<Analyses> {
+    let depths = Day01::parse(source)?;
+    let windows = slide_sum_over(&depths, window).and_then(|sums| analyze(&sums));
+    return Ok(Analyses { window, depths: analyze(&depths)?, windows });
+}
+
+/// Both parts counted one depth at a time, keeping only the last `window`
+/// depths rather than the whole input
+#[derive(Debug)]
+pub struct Sweep {
+    readings: usize,
+    increases: usize,
+    window_increases: usize,
+
+    /// The last `window` depths, the one read `window` readings ago at
+    /// `readings % window`
+    recent: Vec<i32>,
+}
+
+
+impl Sweep {
+
+    /// Count part 2 over windows of three, as the puzzle does
+    pub fn new() -> Sweep {
+        return Sweep::with_window(WINDOW).expect("BUG: the default window is valid");
+    }
+
+    /// Count part 2 over windows of the given size
+    pub fn with_window(window: usize) -> Result<Sweep> {
+        if window == 0 {
+            return Err(Error::puzzle("the window must hold at least one measurement"));
+        }
+        return Ok(Sweep { readings: 0, increases: 0, window_increases: 0, recent: vec![0; window] });
+    }
+
+    /// Count the next depth
+    pub fn push(&mut self, depth: i32) {
+
+        let window = self.recent.len();
+        if self.readings >= 1 && depth > self.recent[(self.readings - 1) % window] {
+            self.increases += 1;
+        }
+
+        let oldest = &mut self.recent[self.readings % window];
+        if self.readings >= window && depth > *oldest {
+            self.window_increases += 1;
+        }
+        *oldest = depth;
+        self.readings += 1;
+
+    }
+
+    /// The answer to part 1 for the depths so far, as `part1` gives it
+    pub fn part1(&self) -> Result<usize> {
+        if self.readings == 0 {
+            return Err(Error::puzzle("no depth measurements were given"));
+        }
+        return Ok(self.increases);
+    }
+
+    /// The answer to part 2 for the depths so far, as `window_increases`
+    /// gives it
+    pub fn part2(&self) -> Result<usize> {
+        check_window(self.readings, self.recent.len())?;
+        return Ok(self.window_increases);
+    }
+
+}
 
+
+impl Default for Sweep {
+    fn default() -> Sweep {
+        return Sweep::new();
+    }
 }
 
-fn part2(input: &Vec<i32>) -> i32 {
 
-    let intermediate = slide_sum_over(input);
-    return part1(&intermediate);
+/// Count both parts from a reader, one line at a time, in constant memory,
+/// with part 2 over windows of the given size. Errors name `path` as the input
+/// they came from.
+///
+/// ```
+/// use std::path::Path;
+/// use aoc_2021_sjunot::day01::sweep;
+///
+/// let sweep = sweep("199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n".as_bytes(), Path::new("-"), 3).unwrap();
+/// assert_eq!(sweep.part1().unwrap(), 7);
+/// assert_eq!(sweep.part2().unwrap(), 5);
+/// ```
+pub fn sweep(mut reader: impl BufRead, path: &Path, window: usize) -> Result<Sweep> {
+
+    let mut sweep = Sweep::with_window(window)?;
+    let mut line = String::new();
+    let mut line_number = 0;
+    loop {
+        line.clear();
+        let read = reader.read_line(&mut line).map_err(|source| Error::Io { path: path.to_path_buf(), source })?;
+        if read == 0 {
+            break;
+        }
+        line_number += 1;
+        let depth = number_on_line(line_number, line.trim_end_matches(['\r', '\n']), "depth").map_err(|e| e.on_line(path, &line))?;
+        if let Some(depth) = depth {
+            sweep.push(depth);
+        }
+    }
+
+    return Ok(sweep);
 
 }
 
-pub fn main(input_path: &Path) {
+/// Solve the requested parts from a reader in constant memory, like `sweep`,
+/// isolating panics as `runner::solve` does. Both parts are counted in the
+/// same pass, so all of the time is reported as parsing.
+///
+/// ```
+/// use std::path::Path;
+/// use aoc_2021_sjunot::day01::solve_stream;
+/// use aoc_2021_sjunot::runner::Parts;
+///
+/// let solution = solve_stream("199\n200\n".as_bytes(), Path::new("-"), Parts::Both, 3).unwrap();
+/// assert_eq!(solution.part1.unwrap().unwrap().answer, "1");
+/// assert!(solution.part2.unwrap().is_err());
+/// ```
+pub fn solve_stream(reader: impl BufRead, path: &Path, parts: Parts, window: usize) -> Result<Solution> {
+
+    let start = Instant::now();
+    let sweep = runner::isolate(|| sweep(reader, path, window))?;
+    let parse = start.elapsed();
+
+    let part = |answer: Result<usize>| answer.map(|a| Part { answer: a.into(), elapsed: Duration::ZERO });
+    let part1 = parts.part1().then(|| part(sweep.part1()));
+    let part2 = parts.part2().then(|| part(sweep.part2()));
+    return Ok(Solution { parse, part1, part2 });
+
+}
+
+
+/// Sonar Sweep
+pub struct Day01;
+
+impl Puzzle for Day01 {
+
+    const DAY: u32 = 1;
+    const TITLE: &'static str = "Sonar Sweep";
+
+    const EXAMPLE: Example = Example {
+        input: "\
+199
+200
+208
+210
+200
+207
+240
+269
+260
+263
+",
+        part1: "7",
+        part2: "5",
+    };
 
-    let part1_input_filename: &String = &input_path.display().to_string();
-    println!("Will read inputs from this file for part 1:  {}", part1_input_filename);
-    let part1_input_string: String = fs::read_to_string(part1_input_filename).expect("Failed to read file contents for part 1 as a string");
-    let part1_input_lines: Vec<&str> = part1_input_string.lines().collect();
-    let part1_input: Vec<i32> = part1_input_lines.iter().map(
-        |l| l.parse().expect("error in input file")
-    ).collect();
-    if part1_input.len() > 0 {  // let empty file mean "skip"
-        let part1_answer = part1(&part1_input);
-        println!("Part 1 answer: {}", part1_answer);
+    type Input = Vec<i32>;
+
+    fn parse(source: &str) -> Result<Vec<i32>> {
+        return numbers_per_line(source, "depth");
+    }
+
+    fn part1(input: &Vec<i32>) -> Result<Answer> {
+        return part1(input).map(Answer::from);
+    }
+
+    fn part2(input: &Vec<i32>) -> Result<Answer> {
+        return part2(input).map(Answer::from);
+    }
+
+}
+
+
+/// Parse the depths and solve the requested parts like `runner::solve`, with
+/// part 2 over windows of the given size
+///
+/// ```
+/// use aoc_2021_sjunot::day01::solve_windowed;
+/// use aoc_2021_sjunot::runner::Parts;
+///
+/// let solution = solve_windowed("199\n200\n208\n210\n200\n207", Parts::Both, 4).unwrap();
+/// assert_eq!(solution.part2.unwrap().unwrap().answer, "2");
+/// ```
+pub fn solve_windowed(source: &str, parts: Parts, window: usize) -> Result<Solution> {
+    return runner::solve_with(
+        source, parts, Day01::parse, Day01::part1,
+        |depths| window_increases(depths, window).map(Answer::from),
+    );
+}
+
+impl Stress for Day01 {
+
+    /// A random walk of at least three depths
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut depth = rng.between(100, 1000);
+        let mut depths: Vec<String> = Vec::new();
+        for _ in 0..size.max(3) {
+            depths.push(depth.to_string());
+            depth += rng.between(-20, 40);
+        }
+        return depths.join("\n") + "\n";
     }
 
-    println!("Part 2 has no input.");
-    let part2_answer = part2(&part1_input);
-    println!("Part 2 answer: {}", part2_answer);
+    fn reference1(input: &Vec<i32>) -> Result<Answer> {
+        let increases = (1..input.len()).filter(|&i| input[i] > input[i - 1]).count();
+        return Ok(increases.into());
+    }
+
+    fn reference2(input: &Vec<i32>) -> Result<Answer> {
+        let window = |i: usize| input[i..i + 3].iter().map(|&d| i64::from(d)).sum::<i64>();
+        let increases = (1..input.len().saturating_sub(2)).filter(|&i| window(i) > window(i - 1)).count();
+        return Ok(increases.into());
+    }
 
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use super::super::stress;
 
     /// The example inputs and outputs given by AOC
     #[test]
     fn test_part1_example() {
-        let example_inputs = [
-            199, // (N/A - no previous measurement)
-            200, // (increased)
-            208, // (increased)
-            210, // (increased)
-            200, // (decreased)
-            207, // (increased)
-            240, // (increased)
-            269, // (increased)
-            260, // (decreased)
-            263, // (increased)
-        ];
-        let output = part1(&example_inputs.to_vec());
-        assert_eq!(7, output);
+        // 199 (N/A - no previous measurement)
+        // 200 (increased)
+        // 208 (increased)
+        // 210 (increased)
+        // 200 (decreased)
+        // 207 (increased)
+        // 240 (increased)
+        // 269 (increased)
+        // 260 (decreased)
+        // 263 (increased)
+        let example_inputs = Day01::parse(Day01::EXAMPLE.input).unwrap();
+        let output = part1(&example_inputs).unwrap();
+        assert_eq!(Answer::from(output), Day01::EXAMPLE.part1);
     }
 
     #[test]
     fn test_part2_example() {
 
-        let example_inputs_1 = [
-            199,  // A
-            200,  // A B
-            208,  // A B C
-            210,  //   B C D
-            200,  // E   C D
-            207,  // E F   D
-            240,  // E F G
-            269,  //   F G H
-            260,  //     G H
-            263,  //       H
-        ];
+        // 199  A
+        // 200  A B
+        // 208  A B C
+        // 210    B C D
+        // 200  E   C D
+        // 207  E F   D
+        // 240  E F G
+        // 269    F G H
+        // 260      G H
+        // 263        H
+        let example_inputs_1 = Day01::parse(Day01::EXAMPLE.input).unwrap();
 
         let example_inputs_2 = [
             607,  // A (N/A - no previous sum)
@@ -114,11 +512,135 @@ mod tests {
             792,  // H (increased)
         ];
 
-        let intermediate = slide_sum_over(&example_inputs_1.to_vec());
+        let intermediate = slide_sum_over(&example_inputs_1, 3).unwrap();
         assert_eq!(intermediate, example_inputs_2);
 
-        let output = part2(&example_inputs_1.to_vec());
-        assert_eq!(5, output);
+        let output = part2(&example_inputs_1).unwrap();
+        assert_eq!(Answer::from(output), Day01::EXAMPLE.part2);
+    }
+
+    /// Comparing the ends of windows counts the same increases as comparing
+    /// their sums, for any size of window
+    #[test]
+    fn test_window_sizes() {
+        for seed in 0..20 {
+            let input = Day01::parse(&stress::generate::<Day01>(seed, 30)).unwrap();
+            for window in 1..=input.len() {
+                let expected = part1(&slide_sum_over(&input, window).unwrap()).unwrap();
+                assert_eq!(window_increases(&input, window).unwrap(), expected, "window of {}", window);
+
+                let mut sweep = Sweep::with_window(window).unwrap();
+                input.iter().for_each(|&depth| sweep.push(depth));
+                assert_eq!(sweep.part2().unwrap(), expected, "window of {}", window);
+            }
+            assert!(matches!(window_increases(&input, input.len() + 1), Err(Error::Puzzle(_))));
+        }
+        assert!(matches!(window_increases(&[1, 2], 0), Err(Error::Puzzle(_))));
+        assert!(matches!(slide_sum_over(&[1, 2], 0), Err(Error::Puzzle(_))));
+        assert!(matches!(Sweep::with_window(0), Err(Error::Puzzle(_))));
+    }
+
+    #[test]
+    fn test_analyze() {
+
+        let analysis = analyze(&[5, 3, 3, 4, 9, 10, 2, 8]).unwrap();
+        assert_eq!(analysis, Analysis {
+            values: 8,
+            increases: 4,
+            decreases: 2,
+            unchanged: 1,
+            longest_run: Run { start: 2, end: 5 },
+            largest_drop: Some(Step { index: 6, change: -8 }),
+            largest_rise: Some(Step { index: 7, change: 6 }),
+            increase_positions: vec![3, 4, 5, 7],
+        });
+
+        let single = analyze(&[7]).unwrap();
+        assert_eq!((single.increases, single.longest_run.length(), single.largest_drop), (0, 1, None));
+        assert!(matches!(analyze::<i32>(&[]), Err(Error::Puzzle(_))));
+
+        // Changes too big for an i64 are errors rather than overflows
+        assert_eq!(analyze(&[i64::MAX, 0, i64::MIN]).unwrap().largest_drop, Some(Step { index: 2, change: i64::MIN }));
+        assert!(matches!(analyze(&[i64::MIN, i64::MAX]), Err(Error::Puzzle(_))));
+        assert!(matches!(analyze(&[0, i64::MAX, -2]), Err(Error::Puzzle(_))));
+
+        // The same analysis of window sums counts part 2's increases
+        let depths = Day01::parse(&stress::generate::<Day01>(3, 200)).unwrap();
+        let windows = analyze(&slide_sum_over(&depths, WINDOW).unwrap()).unwrap();
+        assert_eq!(windows.increases, part2(&depths).unwrap());
+        assert_eq!(windows.increases + windows.decreases + windows.unchanged, windows.values - 1);
+
+    }
+
+    #[test]
+    fn test_sweep_matches_part1_and_part2() {
+        for seed in 0..50 {
+            for size in [0, 1, 2, 3, 4, 50] {
+                let source = stress::generate::<Day01>(seed, size);
+                let source = source.lines().take(size).collect::<Vec<&str>>().join("\r\n");
+                let input = Day01::parse(&source).unwrap();
+                let sweep = sweep(source.as_bytes(), Path::new("-"), WINDOW).unwrap();
+                assert_eq!(sweep.part1().ok(), part1(&input).ok(), "{}", source);
+                assert_eq!(sweep.part2().ok(), part2(&input).ok(), "{}", source);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sweep_errors() {
+        match sweep("199\n2OO\n".as_bytes(), Path::new("depths"), WINDOW) {
+            Err(Error::Parse { file: Some(file), line: 2, column: 1, text: Some(text), .. }) => {
+                assert_eq!(file, Path::new("depths"));
+                assert_eq!(text, "2OO");
+            }
+            other => panic!("expected a parse error, got {:?}", other.map(|_| ())),
+        }
+        assert!(matches!(sweep(&[0xff, b'\n'][..], Path::new("depths"), WINDOW), Err(Error::Io { .. })));
+    }
+
+    /// Too few readings for a part give an error rather than an answer, the
+    /// same whether the input is kept whole or streamed
+    #[test]
+    fn test_tiny_inputs() {
+
+        let cases: [(&str, Option<usize>, Option<usize>); 5] = [
+            ("", None, None),
+            ("199", Some(0), None),
+            ("199\n200", Some(1), None),
+            ("199\n200\n190", Some(1), Some(0)),
+            ("199\n200\n190\n201", Some(2), Some(1)),
+        ];
+        for (source, expected1, expected2) in cases {
+            let input = Day01::parse(source).unwrap();
+            let swept = sweep(source.as_bytes(), Path::new("-"), WINDOW).unwrap();
+            for (actual, expected) in [(part1(&input), expected1), (swept.part1(), expected1), (part2(&input), expected2), (swept.part2(), expected2)] {
+                match (actual, expected) {
+                    (Ok(actual), Some(expected)) => assert_eq!(actual, expected, "{:?}", source),
+                    (Err(Error::Puzzle(_)), None) => (),
+                    (actual, _) => panic!("{:?}: expected {:?}, got {:?}", source, expected, actual),
+                }
+            }
+        }
+
+    }
+
+    #[test]
+    fn test_blank_lines_and_whitespace() {
+        let source = "\n199  \n\t200\r\n\n   \n208\t\n\n";
+        assert_eq!(Day01::parse(source).unwrap(), vec![199, 200, 208]);
+        let swept = sweep(source.as_bytes(), Path::new("-"), WINDOW).unwrap();
+        assert_eq!((swept.part1().unwrap(), swept.part2().unwrap()), (2, 0));
+
+        // Lines still count the blank ones, so errors point at the right line
+        assert!(matches!(Day01::parse("199\n\n2OO"), Err(Error::Parse { line: 3, column: 1, .. })));
+        assert!(matches!(sweep("199\n\n2OO".as_bytes(), Path::new("-"), WINDOW), Err(Error::Parse { line: 3, column: 1, .. })));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(Day01::parse("199\n2OO\n"), Err(Error::Parse { line: 2, column: 1, .. })));
+        assert!(matches!(part1::<i32>(&[]), Err(Error::Puzzle(_))));
+        assert!(matches!(part2(&[199, 200]), Err(Error::Puzzle(_))));
     }
 
 }
\ No newline at end of file
@@ -1,61 +1,77 @@
-// AoC 2021 - Day 2
+//! AoC 2021 - Day 2: Dive!
+//!
+//! Follow a planned course of commands to find where the submarine ends up.
 
-use std::fs;
-use std::path::Path;
+use super::answer::{checked, Answer};
+use super::error::Result;
+use super::puzzle::{Example, Puzzle};
+use super::stress::Stress;
+use super::utils::{keyword_values, Rng};
 
 
-enum Direction {
+/// Which way a command moves the submarine
+pub enum Direction {
     Forward,
     Down,
     Up,
 }
 
 
-struct Command {
-    direction: Direction,
-    amount: i32,
+/// One line of the planned course, e.g. "forward 5"
+pub struct Command {
+    pub direction: Direction,
+    pub amount: i32,
 }
 
 
-struct Position {
-    horizontal: i32,
-    depth: i32,
-    aim: i32,
+/// Where the submarine is, and (for part 2) where it's aiming
+pub struct Position {
+    pub horizontal: i64,
+    pub depth: i64,
+    pub aim: i64,
 }
 
 
 impl Position {
 
-    fn go_part1(&mut self, direction: &Direction, amount: i32) {
+    /// Move by the rules of part 1, where "up" and "down" change the depth
+    pub fn go_part1(&mut self, direction: &Direction, amount: i32) -> Result<()> {
 
+        let amount = i64::from(amount);
         match direction {
-            Direction::Forward => self.horizontal += amount,
-            Direction::Down => self.depth += amount,
-            Direction::Up => self.depth -= amount,
+            Direction::Forward => self.horizontal = checked(self.horizontal.checked_add(amount), "the horizontal position")?,
+            Direction::Down => self.depth = checked(self.depth.checked_add(amount), "the depth")?,
+            Direction::Up => self.depth = checked(self.depth.checked_sub(amount), "the depth")?,
         }
+        return Ok(());
 
     }
 
-    fn go_part2(&mut self, direction: &Direction, amount: i32) {
+    /// Move by the rules of part 2, where "up" and "down" change the aim
+    pub fn go_part2(&mut self, direction: &Direction, amount: i32) -> Result<()> {
 
+        let amount = i64::from(amount);
         if matches!(direction, Direction::Forward) {
-            self.horizontal += amount;
-            self.depth += self.aim * amount;
-            return;
+            self.horizontal = checked(self.horizontal.checked_add(amount), "the horizontal position")?;
+            let dive = checked(self.aim.checked_mul(amount), "the depth")?;
+            self.depth = checked(self.depth.checked_add(dive), "the depth")?;
+            return Ok(());
         }
 
         match direction {
-            Direction::Down => self.aim += amount,
-            Direction::Up => self.aim -= amount,
+            Direction::Down => self.aim = checked(self.aim.checked_add(amount), "the aim")?,
+            Direction::Up => self.aim = checked(self.aim.checked_sub(amount), "the aim")?,
             _ => (),
         }
+        return Ok(());
 
     }
 
 }
 
 
-fn str_to_direction(s: &str) -> Option<Direction> {
+/// Parse a direction, e.g. "forward"
+pub fn str_to_direction(s: &str) -> Option<Direction> {
 
     match s {
         "forward" => Some(Direction::Forward),
@@ -67,7 +83,8 @@ fn str_to_direction(s: &str) -> Option<Direction> {
 }
 
 
-fn part1(input: &Vec<Command>) -> i32 {
+/// Multiply the final horizontal position by the final depth (part 1 rules)
+pub fn part1(input: &[Command]) -> Result<i64> {
 
     let mut position = Position {
         horizontal: 0,
@@ -76,15 +93,16 @@ fn part1(input: &Vec<Command>) -> i32 {
     };
 
     for command in input.iter() {
-        position.go_part1(&command.direction, command.amount);
+        position.go_part1(&command.direction, command.amount)?;
     }
 
-    return position.horizontal * position.depth;
+    return checked(position.horizontal.checked_mul(position.depth), "the answer");
 
 }
 
 
-fn part2(input: &Vec<Command>) -> i32 {
+/// Multiply the final horizontal position by the final depth (part 2 rules)
+pub fn part2(input: &[Command]) -> Result<i64> {
 
     let mut position = Position {
         horizontal: 0,
@@ -93,37 +111,93 @@ fn part2(input: &Vec<Command>) -> i32 {
     };
 
     for command in input.iter() {
-        position.go_part2(&command.direction, command.amount);
+        position.go_part2(&command.direction, command.amount)?;
     }
 
-    return position.horizontal * position.depth;
+    return checked(position.horizontal.checked_mul(position.depth), "the answer");
 
 }
 
 
-pub fn main(input_path: &Path) {
-
-    let part1_input_filename: &String = &input_path.display().to_string();
-    println!("Will read inputs from this file for part 1:  {}", part1_input_filename);
-    let part1_input_string: String = fs::read_to_string(part1_input_filename).expect("Failed to read file contents for part 1 as a string");
-    let part1_input_lines: Vec<&str> = part1_input_string.lines().collect();
-    let part1_input: Vec<Command> = part1_input_lines.iter().map(
-        |l| {
-            // e.g. "forward 1" -> ["forward", "1"]
-            let mut split_line  = l.split_whitespace();
-            let direction_str = split_line.next().expect("error fetching str direction");
-            Command {
-                direction: str_to_direction(&direction_str).expect(&format!("invalid direction: {}", &direction_str)),
-                amount: split_line.next().expect("error fetching int amount").parse().expect("error casting str to int"),
+/// Dive!
+pub struct Day02;
+
+impl Puzzle for Day02 {
+
+    const DAY: u32 = 2;
+    const TITLE: &'static str = "Dive!";
+
+    const EXAMPLE: Example = Example {
+        input: "\
+forward 5
+down 5
+forward 8
+up 3
+down 8
+forward 2
+",
+        part1: "150",
+        part2: "900",
+    };
+
+    type Input = Vec<Command>;
+
+    fn parse(source: &str) -> Result<Vec<Command>> {
+        // e.g. "forward 1"
+        let commands = keyword_values(source, str_to_direction, ("direction", "amount"))?;
+        return Ok(commands.into_iter().map(|(direction, amount)| Command { direction, amount }).collect());
+    }
+
+    fn part1(input: &Vec<Command>) -> Result<Answer> {
+        return part1(input).map(Answer::from);
+    }
+
+    fn part2(input: &Vec<Command>) -> Result<Answer> {
+        return part2(input).map(Answer::from);
+    }
+
+}
+
+
+impl Stress for Day02 {
+
+    /// Commands in any direction, moving between 1 and 9 units
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let directions = ["forward", "down", "up"];
+        let mut course = String::new();
+        for _ in 0..size {
+            course += &format!("{} {}\n", directions[rng.below(directions.len())], rng.between(1, 9));
+        }
+        return course;
+    }
+
+    fn reference1(input: &Vec<Command>) -> Result<Answer> {
+        let (mut horizontal, mut depth) = (0i128, 0i128);
+        for command in input.iter() {
+            let amount = i128::from(command.amount);
+            match command.direction {
+                Direction::Forward => horizontal += amount,
+                Direction::Down => depth += amount,
+                Direction::Up => depth -= amount,
             }
+        }
+        return Ok((horizontal * depth).into());
+    }
 
+    fn reference2(input: &Vec<Command>) -> Result<Answer> {
+        let (mut horizontal, mut depth, mut aim) = (0i128, 0i128, 0i128);
+        for command in input.iter() {
+            let amount = i128::from(command.amount);
+            match command.direction {
+                Direction::Forward => {
+                    horizontal += amount;
+                    depth += aim * amount;
+                }
+                Direction::Down => aim += amount,
+                Direction::Up => aim -= amount,
+            }
         }
-    ).collect();
-    if part1_input.len() > 0 {  // let empty file mean "skip"
-        let part1_answer = part1(&part1_input);
-        println!("Part 1 answer: {}", part1_answer);
-        let part2_answer = part2(&part1_input);
-        println!("Part 2 answer: {}", part2_answer);
+        return Ok((horizontal * depth).into());
     }
 
 }
@@ -132,6 +206,7 @@ pub fn main(input_path: &Path) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use super::super::error::Error;
 
     /// The example inputs and outputs given by AOC
     #[test]
@@ -151,12 +226,12 @@ mod tests {
             aim: 0,
         };
         for command in example_inputs.iter() {
-            position.go_part1(&command.direction, command.amount);
+            position.go_part1(&command.direction, command.amount).unwrap();
         }
         assert_eq!(15, position.horizontal);
         assert_eq!(10, position.depth);
 
-        let output = part1(&example_inputs);
+        let output = part1(&example_inputs).unwrap();
         assert_eq!(150, output);
     }
 
@@ -178,14 +253,41 @@ mod tests {
             aim: 0,
         };
         for command in example_inputs.iter() {
-            position.go_part2(&command.direction, command.amount);
+            position.go_part2(&command.direction, command.amount).unwrap();
         }
         assert_eq!(15, position.horizontal);
         assert_eq!(60, position.depth);
 
-        let output = part2(&example_inputs);
+        let output = part2(&example_inputs).unwrap();
         assert_eq!(900, output);
     }
 
+    #[test]
+    fn test_parse_example() {
+        let input = Day02::parse(Day02::EXAMPLE.input).unwrap();
+        assert_eq!(6, input.len());
+        assert_eq!(Day02::part1(&input).unwrap(), Day02::EXAMPLE.part1);
+        assert_eq!(Day02::part2(&input).unwrap(), Day02::EXAMPLE.part2);
+    }
+
+    #[test]
+    fn test_overflow() {
+        // Both overflowed i32 before answers were widened
+        let input = Day02::parse("forward 100000\ndown 100000").unwrap();
+        assert_eq!(part1(&input).unwrap(), 10_000_000_000);
+        assert_eq!(part2(&input).unwrap(), 0);
+
+        let input = Day02::parse("down 2147483647\nforward 2147483647\nforward 2147483647\nforward 2147483647").unwrap();
+        assert!(matches!(part2(&input), Err(Error::Puzzle(_))));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(Day02::parse("forward 5\nbackward 2"), Err(Error::Parse { line: 2, column: 1, .. })));
+        assert!(matches!(Day02::parse("forward five"), Err(Error::Parse { line: 1, column: 9, .. })));
+        assert!(matches!(Day02::parse("up 3\n\ndown x"), Err(Error::Parse { line: 3, column: 6, .. })));
+        assert_eq!(Day02::parse("up 3\ndown 1\n\n").unwrap().len(), 2);
+        assert!(matches!(Day02::parse("down"), Err(Error::Parse { line: 1, column: 6, .. })));
+    }
 
 }
\ No newline at end of file
@@ -1,8 +1,15 @@
-// AoC 2021 - Day 3
-
-use std::fs;
-use std::path::Path;
-
+//! AoC 2021 - Day 3: Binary Diagnostic
+//!
+//! Decode the submarine's power consumption and life support rating from a
+//! diagnostic report of equally wide binary numbers.
+
+use super::answer::{checked, Answer};
+use super::error::{Error, Result};
+use super::puzzle::{Example, Puzzle};
+use super::stress::Stress;
+use super::utils::{Grid, Rng};
+
+/// The diagnostic report: numbers of `width` bits each
 pub struct Input {
     pub width: usize,
     pub data: Vec<u32>,
@@ -15,21 +22,23 @@ pub struct Input {
 /// Example:
 ///
 /// ```
+/// use aoc_2021_sjunot::day03::{most_common_bit, Input};
+///
 /// let i = Input {
 ///     width: 3 as usize,
 ///     data: vec![
-///         0b010
-///         0b111
-///         0b110
+///         0b010,
+///         0b111,
+///         0b110,
 ///     ],
-/// }
-/// assert_eq!(0b110, most_common_bit(&i, None));
+/// };
+/// assert_eq!(0b110, most_common_bit(&i, None).unwrap());
 /// ```
 ///
 /// A "tie breaker" can be given if there's a tie between 0 or 1 for a column.
 /// The value is either 0 or 1, whichever will win the tie by default. If there
-/// is a tie without a tie breaker, the code will panic.
-pub fn most_common_bit(input: &Input, tie_breaker: Option<u32>) -> u32 {
+/// is a tie without a tie breaker, an error is returned.
+pub fn most_common_bit(input: &Input, tie_breaker: Option<u32>) -> Result<u32> {
 
     let mut counters = vec![0; input.width];
 
@@ -46,12 +55,12 @@ pub fn most_common_bit(input: &Input, tie_breaker: Option<u32>) -> u32 {
 
     let value_parts = counters.iter().rev().map(
         |val| {
-            if *val > 0 { 1 }
-            else if *val < 0 { 0 }
+            if *val > 0 { Ok(1) }
+            else if *val < 0 { Ok(0) }
             else {
                 // tie!
-                let tb = tie_breaker.expect("tie without a tie breaker");
-                if tb == 1 { 1 } else { 0 }
+                let tb = tie_breaker.ok_or(Error::puzzle("tie without a tie breaker"))?;
+                if tb == 1 { Ok(1) } else { Ok(0) }
             }
         }
     );
@@ -59,49 +68,52 @@ pub fn most_common_bit(input: &Input, tie_breaker: Option<u32>) -> u32 {
     let mut gamma_rate: u32 = 0;
     for part in value_parts {
         gamma_rate <<= 1;
-        gamma_rate += part;
+        gamma_rate += part?;
     }
-    return gamma_rate;
+    return Ok(gamma_rate);
 
 }
 
 
-mod part1 {
+/// Power consumption
+pub mod part1 {
 
     use super::*;
 
-    pub fn input(source: &str) -> Input {
-        let lines: Vec<&str> = source.lines().collect();
-        let width: usize = lines[0].len();
-        let data: Vec<u32> = lines.iter().map(
-            |line| {
-                isize::from_str_radix(line, 2).expect("failed to cast to int") as u32
-            }
-        ).collect();
-        return Input { width, data };
+    /// Parse the diagnostic report, one binary number per line
+    pub fn input(source: &str) -> Result<Input> {
+        let bits = Grid::parse_chars(source, 1, |c| c.to_digit(2).ok_or(String::from("expected a binary digit")))?;
+        let width = bits.width();
+        if width == 0 || width > 32 {
+            return Err(Error::parse(1, 1, "expected between 1 and 32 bits"));
+        }
+        let data: Vec<u32> = bits.rows().map(|row| row.iter().fold(0, |value, bit| (value << 1) | bit)).collect();
+        return Ok(Input { width, data });
     }
 
-    pub fn solution(input: &Input) -> u32 {
+    /// Multiply the gamma rate by the epsilon rate
+    pub fn solution(input: &Input) -> Result<u64> {
 
-        let gamma_rate = most_common_bit(input, None);
-        let mask = (1 << input.width) - 1;
+        let gamma_rate = most_common_bit(input, None)?;
+        let mask = u32::MAX >> (32 - input.width);
         let epsilon_rate: u32 = !gamma_rate & mask;
 
-        return gamma_rate * epsilon_rate;
+        return checked(u64::from(gamma_rate).checked_mul(epsilon_rate.into()), "the power consumption");
 
     }
 
 }
 
 
-mod part2 {
+/// Life support rating
+pub mod part2 {
 
     use super::*;
 
-    fn reduce(input: &Input, pos: u32, lcb: bool) -> u32 {
+    fn reduce(input: &Input, pos: u32, lcb: bool) -> Result<u32> {
 
-        let mask: u32 = (1 << input.width - 1) >> pos;
-        let mut mcb: u32 = most_common_bit(input, Some(1));
+        let mask: u32 = (1 << (input.width - 1)) >> pos;
+        let mut mcb: u32 = most_common_bit(input, Some(1))?;
 
         if lcb {  // filter by least-common bit instead of mcb
             mcb = !mcb & mask
@@ -111,37 +123,155 @@ mod part2 {
             |int| {
                 mask & *int == mask & mcb
             }
-        ).map(|x| *x).collect();
+        ).copied().collect();
 
         if filtered.len() > 1 {
+            if pos as usize + 1 >= input.width {
+                return Err(Error::puzzle("duplicate diagnostic values can't be reduced to one rating"));
+            }
             let new_input = Input { width: input.width, data: filtered };
             return reduce(&new_input, pos + 1, lcb);
         }
 
-        return filtered[0];
+        return filtered.first().copied().ok_or(Error::puzzle("no diagnostic values were given"));
 
     }
 
-    pub fn solution(input: &Input) -> u32 {
+    /// Multiply the oxygen generator rating by the CO2 scrubber rating
+    pub fn solution(input: &Input) -> Result<u64> {
 
-        let oxygen_generator_rating = reduce(input, 0, false);
-        let co2_scrubber_rating = reduce(input, 0, true);
+        let oxygen_generator_rating = reduce(input, 0, false)?;
+        let co2_scrubber_rating = reduce(input, 0, true)?;
 
-        return oxygen_generator_rating * co2_scrubber_rating;
+        return checked(u64::from(oxygen_generator_rating).checked_mul(co2_scrubber_rating.into()), "the life support rating");
 
     }
 
 }
 
 
-pub fn main(input_path: &Path) {
+/// Binary Diagnostic
+pub struct Day03;
+
+impl Puzzle for Day03 {
+
+    const DAY: u32 = 3;
+    const TITLE: &'static str = "Binary Diagnostic";
+
+    const EXAMPLE: Example = Example {
+        input: "\
+00100
+11110
+10110
+10111
+10101
+01111
+00111
+11100
+10000
+11001
+00010
+01010
+",
+        part1: "198",
+        part2: "230",
+    };
+
+    type Input = Input;
+
+    fn parse(source: &str) -> Result<Input> {
+        return part1::input(source);
+    }
+
+    fn part1(input: &Input) -> Result<Answer> {
+        return part1::solution(input).map(Answer::from);
+    }
+
+    fn part2(input: &Input) -> Result<Answer> {
+        return part2::solution(input).map(Answer::from);
+    }
+
+}
+
+
+/// Add `count` distinct values of `bits` bits after the given prefix, split so
+/// that any two or more values sharing a prefix differ in the next bit. That
+/// keeps both ratings of part 2 reducible to a single value.
+fn spread(rng: &mut Rng, prefix: u64, bits: usize, count: usize, values: &mut Vec<u64>) {
+
+    if count == 1 {
+        values.push((prefix << bits) | (rng.next_u64() & ((1 << bits) - 1)));
+        return;
+    }
+
+    let half = 1 << (bits - 1);
+    let zeros = rng.between(count.saturating_sub(half).max(1) as i64, (count - 1).min(half) as i64) as usize;
+    spread(rng, prefix << 1, bits - 1, zeros, values);
+    spread(rng, (prefix << 1) | 1, bits - 1, count - zeros, values);
+
+}
+
+
+/// Count the values with the given bit set
+fn ones(values: &[u32], bit: usize) -> usize {
+    return values.iter().filter(|&&v| v & (1 << bit) != 0).count();
+}
+
+
+impl Stress for Day03 {
+
+    /// An odd number of distinct values, so no column is ever tied in part 1
+    fn generate(rng: &mut Rng, size: usize) -> String {
+
+        let count = size.max(1) | 1;
+        let min_width = (usize::BITS - (count - 1).leading_zeros()).max(1) as i64;
+        let width = rng.between(min_width, min_width.max(12)) as usize;
+
+        let mut values: Vec<u64> = Vec::with_capacity(count);
+        spread(rng, 0, width, count, &mut values);
+        rng.shuffle(&mut values);
+
+        return values.iter().map(|v| format!("{:0width$b}\n", v, width = width)).collect();
 
-    let part1_input_source: String = fs::read_to_string(input_path).expect("failed to read part 1 input");
-    let part1_input = part1::input(&part1_input_source);
-    let part1_output = part1::solution(&part1_input);
-    println!("Part 1 answer: {}", part1_output);
-    let part2_output = part2::solution(&part1_input);
-    println!("Part 2 answer: {}", part2_output);
+    }
+
+    fn reference1(input: &Input) -> Result<Answer> {
+
+        let (mut gamma, mut epsilon) = (0i128, 0i128);
+        for bit in (0..input.width).rev() {
+            let ones = ones(&input.data, bit);
+            let zeros = input.data.len() - ones;
+            if ones == zeros {
+                return Err(Error::puzzle("tie without a tie breaker"));
+            }
+            gamma = gamma * 2 + i128::from(ones > zeros);
+            epsilon = epsilon * 2 + i128::from(ones < zeros);
+        }
+        return Ok((gamma * epsilon).into());
+
+    }
+
+    fn reference2(input: &Input) -> Result<Answer> {
+
+        let rating = |keep_common: bool| {
+            let mut values = input.data.clone();
+            for bit in (0..input.width).rev() {
+                if values.len() == 1 {
+                    break;
+                }
+                let ones = ones(&values, bit);
+                let zeros = values.len() - ones;
+                let keep = if keep_common { ones >= zeros } else { ones < zeros };
+                values.retain(|&v| (v & (1 << bit) != 0) == keep);
+            }
+            return match values.as_slice() {
+                [value] => Ok(i128::from(*value)),
+                _ => Err(Error::puzzle("the rating can't be reduced to one value")),
+            };
+        };
+        return Ok((rating(true)? * rating(false)?).into());
+
+    }
 
 }
 
@@ -149,32 +279,37 @@ pub fn main(input_path: &Path) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use super::super::utils;
 
     /// The example inputs and outputs given by AOC
     #[test]
     fn test_part1_example() {
 
-        let example_input = "
-            00100
-            11110
-            10110
-            10111
-            10101
-            01111
-            00111
-            11100
-            10000
-            11001
-            00010
-            01010
-        ";
-        let example_input_cleaned = utils::remove_multiline_whitespace(&example_input);
-        let part1_input = part1::input(&example_input_cleaned);
-        let part1_output = part1::solution(&part1_input);
-        assert_eq!(part1_output, 198);
-        let part2_output = part2::solution(&part1_input);
-        assert_eq!(part2_output, 230);
+        let part1_input = part1::input(Day03::EXAMPLE.input).unwrap();
+        let part1_output = part1::solution(&part1_input).unwrap();
+        assert_eq!(Answer::from(part1_output), Day03::EXAMPLE.part1);
+        let part2_output = part2::solution(&part1_input).unwrap();
+        assert_eq!(Answer::from(part2_output), Day03::EXAMPLE.part2);
+    }
+
+    /// Answers for 32 bit values don't fit in 32 bits
+    #[test]
+    fn test_wide_answers() {
+        let input = part1::input("11111111111111110000000000000000").unwrap();
+        assert_eq!(part1::solution(&input).unwrap(), 281466386841600);
+        let input = part1::input("11111111111111111111111111111111\n01111111111111111111111111111111").unwrap();
+        assert_eq!(part2::solution(&input).unwrap(), 9223372030412324865);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(part1::input(""), Err(Error::Parse { line: 1, .. })));
+        assert!(matches!(part1::input("0101\n0121"), Err(Error::Parse { line: 2, column: 3, .. })));
+        assert!(matches!(part1::input("0101\n011"), Err(Error::Parse { line: 2, column: 4, .. })));
+        assert!(matches!(part1::input("0101\n01101"), Err(Error::Parse { line: 2, column: 5, .. })));
+        let tied = part1::input("01\n10").unwrap();
+        assert!(matches!(part1::solution(&tied), Err(Error::Puzzle(_))));
+        let duplicated = part1::input("01\n01").unwrap();
+        assert!(matches!(part2::solution(&duplicated), Err(Error::Puzzle(_))));
     }
 
 }
\ No newline at end of file
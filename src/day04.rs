@@ -1,20 +1,33 @@
-// AoC 2021 - Day 4
+//! AoC 2021 - Day 4: Giant Squid
+//!
+//! Play bingo against a giant squid: find the board that wins first (part 1)
+//! and the board that wins last (part 2).
 
 use std::collections::HashMap;
-use std::fs;
-use std::path::Path;
 
+use super::answer::{checked, Answer};
+use super::error::{Error, Result};
+use super::puzzle::{Example, Puzzle};
+use super::stress::Stress;
+use super::utils::{blocks, comma_separated, matrix, Grid, Rng};
 
-mod bingo {
+
+/// Boards and the game of Bingo played on them
+pub mod bingo {
 
     pub const ROWS: usize = 5;
     pub const COLS: usize = 5;
 
     use super::*;
 
+    /// The numbers to be drawn, in order, and the boards playing along
     pub struct Input {
         pub numbers: Vec<u32>,
         pub boards: Vec<Board>,
+
+        /// The boards that win, soonest first, as predicted when the input is
+        /// parsed so that both parts share one game
+        pub predictions: Vec<Prediction>,
     }
 
     struct Cell {
@@ -22,59 +35,67 @@ mod bingo {
         hit: bool,
     }
 
+    /// A 5x5 Bingo board
     pub struct Board {
-        pub grid: [[u32; COLS]; ROWS],
+        pub grid: Grid<u32>,
     }
 
     /// How a board will perform given a sequence of numbers to be played in a
     /// game of Bingo. Predictions for Boards can be compared against one
     /// another to determine which would win a given game, at what time, and
     /// with what score.
-    pub struct Prediction<'board> {
+    pub struct Prediction {
         pub when: usize,
-        pub score: u32,
-        pub board: &'board Board,
+
+        /// Sum of the numbers left unmarked when the board wins
+        pub unmarked: u64,
+
+        /// The number that completes the board
+        pub number: u32,
+    }
+
+    impl Prediction {
+
+        /// The board's score: its unmarked numbers times the winning number
+        pub fn score(&self) -> Result<u64> {
+            return checked(self.unmarked.checked_mul(self.number.into()), "the score");
+        }
+
     }
 
     impl Board {
 
         /// Check the given coordinate's row and column for Bingo!
-        fn check(&self, cells: &[[Cell; bingo::COLS]; bingo::ROWS], coord: &(usize, usize)) -> bool {
-            if cells.iter().filter( |row| { row[coord.1].hit } ).count() == bingo::ROWS {
+        fn check(&self, cells: &Grid<Cell>, coord: &(usize, usize)) -> bool {
+            if cells.column(coord.1).is_some_and(|mut column| column.all(|cell| cell.hit)) {
                 return true;
             }
-            if cells[coord.0].iter().filter( |col| { col.hit } ).count() == bingo::COLS {
+            if cells.row(coord.0).is_some_and(|row| row.iter().all(|cell| cell.hit)) {
                 return true;
             }
             return false;
         }
 
-        /// Compute the board's current score given the winning number
-        fn score(&self, cells: &[[Cell; bingo::COLS]; bingo::ROWS], number: u32) -> u32 {
-            let sum: u32 = cells
+        /// Sum the board's unmarked numbers, which can't overflow for a
+        /// board of 5x5 32-bit numbers
+        fn unmarked(&self, cells: &Grid<Cell>) -> u64 {
+            return cells
                 .iter()
-                .flat_map(|row| { row.iter() })
                 .filter(|cell| { !cell.hit })
-                .map(|cell| { cell.value })
+                .map(|cell| { u64::from(cell.value) })
                 .sum();
-            return sum * number;
         }
 
         /// Map all values on the board to their coordinate.
         /// This builds an index for looking up coordinates by value.
-        fn index(&self, cells: &[[Cell; bingo::COLS]; bingo::ROWS]) -> HashMap<u32, Vec<(usize, usize)>> {
+        fn index(&self, cells: &Grid<Cell>) -> HashMap<u32, Vec<(usize, usize)>> {
 
             let mut values_to_coords: HashMap<u32, Vec<(usize, usize)>> = HashMap::new();
-            for row_idx in 0..bingo::ROWS {
-                let row = &cells[row_idx];
-                for col_idx in 0..bingo::COLS {
-                    let coord = (row_idx, col_idx);
-                    let col = &row[col_idx];
-                    let vec_of_coords = values_to_coords
-                        .entry(col.value)
-                        .or_insert(Vec::<(usize, usize)>::new());
-                    vec_of_coords.push(coord);
-                }
+            for (coord, cell) in cells.positions() {
+                let vec_of_coords = values_to_coords
+                    .entry(cell.value)
+                    .or_default();
+                vec_of_coords.push(coord);
             }
 
             return values_to_coords;
@@ -84,15 +105,11 @@ mod bingo {
         ///  1. Whether the board will hit Bingo!, and if so
         ///  2. Which number (by index/position) triggered the bingo, and
         ///  3. Which board cells were hit & not hit (by coordinate, row/col)
-        pub fn predict(&self, numbers: &Vec<u32>) -> Option<Prediction> {  // bingo
+        pub fn predict(&self, numbers: &[u32]) -> Option<Prediction> {  // bingo
 
-            let mut cells: [[Cell; bingo::COLS]; bingo::ROWS] = self.grid.map(
-                |row| {
-                    row.map(
-                        |col| {
-                            Cell { value: col, hit: false, }
-                        }
-                    )
+            let mut cells: Grid<Cell> = self.grid.map(
+                |&value| {
+                    Cell { value, hit: false, }
                 }
             );
 
@@ -110,12 +127,12 @@ mod bingo {
 
                 // Mark matching cells as hit and check for Bingo!
                 for coord in vec_of_coords {
-                    cells[coord.0][coord.1].hit = true;
-                    if self.check(&cells, &coord) {
+                    cells[*coord].hit = true;
+                    if self.check(&cells, coord) {
                         return Some(Prediction {
                             when: number_idx,
-                            score: self.score(&cells, number),
-                            board: self,
+                            unmarked: self.unmarked(&cells),
+                            number,
                         })
                     }
                 }
@@ -129,12 +146,13 @@ mod bingo {
 
 }
 
-mod part1 {
+/// The first board to win
+pub mod part1 {
 
     use super::*;
 
     /// Given a sequence of strings like this:
-    /// ```
+    /// ```text
     /// let seq = vec![
     ///    "22 13 17 11  0
     ///      8  2 23  4 24
@@ -143,107 +161,211 @@ mod part1 {
     ///     1 12 20 15 19",
     /// ];
     /// ```
-    /// Create column-major grids and store them in a bingo::Board.
-    fn boards(source: &[&str]) -> Vec<bingo::Board> {
-
-        // Get a sequence matrices that are hopefully 5x5 Bingo boards.
-        let boards_from_strs: Vec<Vec<Vec<u32>>> = source.iter().map(
-            |block| {
-                block.split('\n').map(
-                    |line| {
-                        line.split_ascii_whitespace().map(
-                            |n| { n.parse().unwrap() }
-                        ).collect()
-                    }
-                ).collect()
+    /// Parse each into a 5x5 grid and store them in a bingo::Board. Each block
+    /// is given with the (1-based) line of the input it starts on.
+    fn boards(source: &[(usize, &str)]) -> Result<Vec<bingo::Board>> {
+
+        let mut boards: Vec<bingo::Board> = Vec::new();
+        for (block_line, block) in source.iter() {
+            let grid: Grid<u32> = matrix(block, *block_line, "number")?;
+
+            // Point just past the first row if the rows are the wrong width
+            if grid.height() > 0 && grid.width() != bingo::COLS {
+                let (line_idx, line) = block.lines().enumerate().find(|(_, l)| !l.trim().is_empty()).unwrap_or((0, ""));
+                let column = line.trim_end().chars().count() + 1;
+                return Err(Error::parse(block_line + line_idx, column, format!("expected {} numbers per row, got {}", bingo::COLS, grid.width())));
             }
-        ).collect();
-
-        // Convert each matrix parsed from the input into a fixed array of ints
-        // (which have performance benefits from their const size).
-        let mut grids: Vec<[[u32; bingo::COLS]; bingo::ROWS]> = Vec::new();
-        for board_from_str in boards_from_strs.iter() {
-            let mut grid: [[u32; bingo::COLS]; bingo::ROWS] = [[0; bingo::COLS]; bingo::ROWS];
-            for row_idx in 0..bingo::ROWS {
-                for col_idx in 0..bingo::COLS {
-                    grid[row_idx][col_idx] = board_from_str[row_idx][col_idx];
-                }
+            if grid.height() != bingo::ROWS {
+                return Err(Error::parse(*block_line, 1, format!("expected {} rows per board, got {}", bingo::ROWS, grid.height())));
             }
-            grids.push(grid);
+            boards.push(bingo::Board { grid });
         }
 
-        // Create the bingo::Boards
-        let boards: Vec<bingo::Board> = grids.into_iter().map(
-            |grid| {
-                bingo::Board {
-                    grid: grid,
-                }
-            }
-        ).collect();
-
-        return boards;
+        return Ok(boards);
     }
 
     /// Parse the part1 input (which might be reused in part2) into the sequence
     /// of "numbers" to be played and a collection of Bingo "boards".
-    pub fn input(source: &str) -> bingo::Input {
-        let lines: Vec<&str> = source.split("\n\n").collect();
-        let numbers: Vec<u32> = lines[0].split(',').map( |n| { n.trim().parse().unwrap() }).collect();
-        let boards: Vec<bingo::Board> = boards(&lines[1..]);
-        return bingo::Input {
-            numbers: numbers,
-            boards: boards,
+    pub fn input(source: &str) -> Result<bingo::Input> {
+
+        let blocks = blocks(source);
+        let (numbers_line, numbers_block) = *blocks.first().ok_or(Error::parse(1, 1, "expected the numbers to draw"))?;
+        let mut lines = numbers_block.lines();
+        let numbers: Vec<u32> = comma_separated(numbers_line, lines.next().unwrap_or(""), "number to draw")?;
+        if lines.next().is_some() {
+            return Err(Error::parse(numbers_line + 1, 1, "expected a blank line after the numbers to draw"));
         }
+        let boards: Vec<bingo::Board> = boards(&blocks[1..])?;
+        let predictions = predict(&numbers, &boards);
+        return Ok(bingo::Input {
+            numbers,
+            boards,
+            predictions,
+        });
     }
 
     /// Given the numbers and boards to be played at Bingo, determine when each
-    /// board will win and with what score (if it ever wins). Return the score
-    /// for the Bingo board that will win the soonest.
-    pub fn predict(input: &bingo::Input) -> Vec<bingo::Prediction> {
+    /// board will win and with what score (if it ever wins), soonest first.
+    pub fn predict(numbers: &[u32], boards: &[bingo::Board]) -> Vec<bingo::Prediction> {
 
         // Running the numbers over each board will return a "prediction" with
         // the win time and score. Filter out losing boards (None) and unpack
         // the Some(prediction) to get a sequence of Predictions.
-        let mut predictions = input.boards.iter()
-            .map( |board| { board.predict(&input.numbers) } )
-            .filter( |prediction| prediction.is_some() )
-            .map( |prediction| prediction.unwrap() )
+        let mut predictions = boards.iter()
+            .filter_map( |board| { board.predict(numbers) } )
             .collect::<Vec<bingo::Prediction>>();
 
         // Sort the results in ascending order of when they'll win the game.
-        predictions.sort_by(|a, b| a.when.cmp(&b.when) );
+        predictions.sort_by_key(|prediction| prediction.when);
 
-        // Return the score of the board that will win the soonest.
         return predictions;
 
     }
 
     /// Returns the solution for part 1
-    pub fn solution(predictions: &Vec<bingo::Prediction>) -> u32 {
-        return predictions[0].score;
+    pub fn solution(predictions: &[bingo::Prediction]) -> Result<u64> {
+        return predictions.first().ok_or(Error::puzzle("no board ever wins"))?.score();
     }
 
 }
 
-mod part2 {
+/// The last board to win
+pub mod part2 {
 
     use super::*;
 
-    pub fn solution(predictions: &Vec<bingo::Prediction>) -> u32 {
-        return predictions.last().unwrap().score;
+    /// Returns the solution for part 2
+    pub fn solution(predictions: &[bingo::Prediction]) -> Result<u64> {
+        return predictions.last().ok_or(Error::puzzle("no board ever wins"))?.score();
+    }
+
+}
+
+/// Giant Squid
+pub struct Day04;
+
+impl Puzzle for Day04 {
+
+    const DAY: u32 = 4;
+    const TITLE: &'static str = "Giant Squid";
+
+    const EXAMPLE: Example = Example {
+        input: "\
+7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+22 13 17 11  0
+ 8  2 23  4 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19
+
+ 3 15  0  2 22
+ 9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6
+
+14 21 17 24  4
+10 16 15  9 19
+18  8 23 26 20
+22 11 13  6  5
+ 2  0 12  3  7
+",
+        part1: "4512",
+        part2: "1924",
+    };
+
+    type Input = bingo::Input;
+
+    fn parse(source: &str) -> Result<bingo::Input> {
+        return part1::input(source);
+    }
+
+    fn part1(input: &bingo::Input) -> Result<Answer> {
+        return part1::solution(&input.predictions).map(Answer::from);
+    }
+
+    fn part2(input: &bingo::Input) -> Result<Answer> {
+        return part2::solution(&input.predictions).map(Answer::from);
+    }
+
+}
+
+
+/// Play the whole game number by number, returning the score of every win in
+/// the order the boards win
+fn play(input: &bingo::Input) -> Vec<i128> {
+
+    let mut marked = vec![[[false; bingo::COLS]; bingo::ROWS]; input.boards.len()];
+    let mut won = vec![false; input.boards.len()];
+    let mut scores: Vec<i128> = Vec::new();
+
+    for &number in input.numbers.iter() {
+        for (board_idx, board) in input.boards.iter().enumerate() {
+            if won[board_idx] {
+                continue;
+            }
+            for row in 0..bingo::ROWS {
+                for col in 0..bingo::COLS {
+                    if board.grid[(row, col)] == number {
+                        marked[board_idx][row][col] = true;
+                    }
+                }
+            }
+            let marks = &marked[board_idx];
+            let full_row = (0..bingo::ROWS).any(|row| (0..bingo::COLS).all(|col| marks[row][col]));
+            let full_col = (0..bingo::COLS).any(|col| (0..bingo::ROWS).all(|row| marks[row][col]));
+            if full_row || full_col {
+                won[board_idx] = true;
+                let mut unmarked = 0;
+                for row in 0..bingo::ROWS {
+                    for col in 0..bingo::COLS {
+                        if !marks[row][col] {
+                            unmarked += i128::from(board.grid[(row, col)]);
+                        }
+                    }
+                }
+                scores.push(unmarked * i128::from(number));
+            }
+        }
     }
 
+    return scores;
+
 }
 
-pub fn main(input_path: &Path) {
 
-    let part1_input_source: String = fs::read_to_string(input_path).expect("failed to read part 1 input");
-    let part1_input = part1::input(&part1_input_source);
-    let part1_predictions = part1::predict(&part1_input);
-    let part1_output = part1::solution(&part1_predictions);
-    println!("Part 1 answer: {}", part1_output);
-    let part2_output = part2::solution(&part1_predictions);
-    println!("Part 2 answer: {}", part2_output);
+impl Stress for Day04 {
+
+    /// Boards of distinct numbers, with every number eventually drawn so that
+    /// every board wins
+    fn generate(rng: &mut Rng, size: usize) -> String {
+
+        let pool = bingo::ROWS * bingo::COLS + rng.below(75);
+        let mut numbers: Vec<usize> = (0..pool).collect();
+        rng.shuffle(&mut numbers);
+        let mut source = numbers.iter().map(|n| n.to_string()).collect::<Vec<String>>().join(",") + "\n";
+
+        for _ in 0..size.max(1) {
+            rng.shuffle(&mut numbers);
+            source += "\n";
+            for row in numbers[..bingo::ROWS * bingo::COLS].chunks(bingo::COLS) {
+                source += &row.iter().map(|n| format!("{:>2}", n)).collect::<Vec<String>>().join(" ");
+                source += "\n";
+            }
+        }
+
+        return source;
+
+    }
+
+    fn reference1(input: &bingo::Input) -> Result<Answer> {
+        return play(input).first().map(|&score| Answer::from(score)).ok_or(Error::puzzle("no board ever wins"));
+    }
+
+    fn reference2(input: &bingo::Input) -> Result<Answer> {
+        return play(input).last().map(|&score| Answer::from(score)).ok_or(Error::puzzle("no board ever wins"));
+    }
 
 }
 
@@ -251,38 +373,17 @@ pub fn main(input_path: &Path) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use super::super::utils;
     use super::bingo::Board;
 
     /// The example inputs and outputs given by AOC
     #[test]
     fn test_part1_example() {
 
-        let example_input = "
-        7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
-
-        22 13 17 11  0
-         8  2 23  4 24
-        21  9 14 16  7
-         6 10  3 18  5
-         1 12 20 15 19
-
-         3 15  0  2 22
-         9 18 13 17  5
-        19  8  7 25 23
-        20 11 10 24  4
-        14 21 16 12  6
-
-        14 21 17 24  4
-        10 16 15  9 19
-        18  8 23 26 20
-        22 11 13  6  5
-         2  0 12  3  7
-        ";
-        let example_input_cleaned = utils::remove_multiline_whitespace(&example_input);
-        let part1_input = part1::input(&example_input_cleaned);
-        let part1_output = part1::solution(&part1::predict(&part1_input));
-        assert_eq!(part1_output, 4512);
+        let part1_input = part1::input(Day04::EXAMPLE.input).unwrap();
+        let part1_output = part1::solution(&part1_input.predictions).unwrap();
+        assert_eq!(Answer::from(part1_output), Day04::EXAMPLE.part1);
+        let part2_output = part2::solution(&part1_input.predictions).unwrap();
+        assert_eq!(Answer::from(part2_output), Day04::EXAMPLE.part2);
 
     }
 
@@ -291,35 +392,72 @@ mod tests {
 
         let mut input = bingo::Input {
             numbers: vec![],
+            predictions: vec![],
             boards: vec![
                 Board {
-                    grid: [
-                        [0,  0,  0,  1, 11],  // wins by column, score = 11
-                        [0,  0,  0,  1,  0],
-                        [0,  0,  0,  1,  0],
-                        [0,  0,  0,  1,  0],
-                        [0,  0,  0,  1,  0],
-                    ]
+                    grid: Grid::from_rows(vec![
+                        vec![0,  0,  0,  1, 11],  // wins by column, score = 11
+                        vec![0,  0,  0,  1,  0],
+                        vec![0,  0,  0,  1,  0],
+                        vec![0,  0,  0,  1,  0],
+                        vec![0,  0,  0,  1,  0],
+                    ]).unwrap()
                 },
                 Board{
-                    grid: [
-                        [0,  0,  0,  0, 11],  // wins by row, score = 22
-                        [0,  0,  0,  0,  0],
-                        [0,  0,  0,  0,  0],
-                        [2,  2,  2,  2,  2],
-                        [0,  0,  0,  0,  0],
-                    ]
+                    grid: Grid::from_rows(vec![
+                        vec![0,  0,  0,  0, 11],  // wins by row, score = 22
+                        vec![0,  0,  0,  0,  0],
+                        vec![0,  0,  0,  0,  0],
+                        vec![2,  2,  2,  2,  2],
+                        vec![0,  0,  0,  0,  0],
+                    ]).unwrap()
                 }
             ],
         };
 
         input.numbers.push(1);
-        assert_eq!(part1::solution(&part1::predict(&input)), 11);
+        assert_eq!(part1::solution(&part1::predict(&input.numbers, &input.boards)).unwrap(), 11);
 
         input.numbers.clear();
         input.numbers.push(2);
-        assert_eq!(part1::solution(&part1::predict(&input)), 22);
+        assert_eq!(part1::solution(&part1::predict(&input.numbers, &input.boards)).unwrap(), 22);
+
+        input.numbers.clear();
+        input.numbers.push(3);
+        assert!(matches!(part1::solution(&part1::predict(&input.numbers, &input.boards)), Err(Error::Puzzle(_))));
+
+    }
+
+    #[test]
+    fn test_wide_scores() {
+
+        let board = |winning: u32, rest: u32| {
+            let mut rows = vec![vec![winning; bingo::COLS]];
+            rows.extend(vec![vec![rest; bingo::COLS]; bingo::ROWS - 1]);
+            return Board { grid: Grid::from_rows(rows).unwrap() };
+        };
 
+        assert_eq!(part1::solution(&part1::predict(&[7], &[board(7, u32::MAX)])).unwrap(), 601295421300);
+        let wide = part1::predict(&[u32::MAX], &[board(u32::MAX, u32::MAX - 1)]);
+        assert!(matches!(part1::solution(&wide), Err(Error::Puzzle(_))));
+
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let board = "1 2 3 4 5\n6 7 8 9 10\n11 12 13 14 15\n16 17 18 19 20\n21 22 23 24 25";
+        assert!(part1::input(&format!("1,2\n\n{}\n", board)).is_ok());
+        assert!(matches!(part1::input(&format!("1, x\n\n{}", board)), Err(Error::Parse { line: 1, column: 4, .. })));
+        assert!(matches!(
+            part1::input(&format!("1,2\n\n{}\n\n{}", board, board.replace("13", "1e"))),
+            Err(Error::Parse { line: 11, column: 7, .. }),
+        ));
+        assert!(matches!(
+            part1::input(&format!("1,2\n\n{}\n\n1 2 3", board)),
+            Err(Error::Parse { line: 9, column: 6, .. }),
+        ));
+        assert!(matches!(part1::input("\n"), Err(Error::Parse { line: 1, column: 1, .. })));
+        assert!(matches!(part1::input(&format!("1,2\n{}", board)), Err(Error::Parse { line: 2, column: 1, .. })));
     }
 
 }
\ No newline at end of file
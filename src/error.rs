@@ -0,0 +1,218 @@
+//! The errors shared by every day and the runner
+
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+
+/// Everything that can go wrong while reading, parsing or solving a puzzle
+#[derive(Debug)]
+pub enum Error {
+
+    /// The command line didn't make sense, e.g. a day that isn't completed
+    Usage(String),
+
+    /// The input couldn't be read
+    Io { path: PathBuf, source: io::Error },
+
+    /// The input isn't in the format the puzzle describes. Lines and columns
+    /// count from 1; the file and the text of the offending line are filled in
+    /// by `Error::in_file` (or `Error::on_line`) once the error reaches whoever
+    /// read the input.
+    Parse {
+        file: Option<PathBuf>,
+        line: usize,
+        column: usize,
+        text: Option<String>,
+        message: String,
+    },
+
+    /// The input is well formed but breaks one of the puzzle's rules, e.g. a
+    /// game of bingo that no board ever wins
+    Puzzle(String),
+
+    /// The solver panicked; the panic was caught so that other days can still
+    /// run. The location is the file, line and column of the panic.
+    Panic { message: String, location: Option<String> },
+
+}
+
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+
+impl Error {
+
+    /// Build a parse error for a position in the input
+    pub fn parse(line: usize, column: usize, message: impl Into<String>) -> Error {
+        return Error::Parse { file: None, line, column, text: None, message: message.into() };
+    }
+
+    /// Build a parse error pointing at a token
+    ///
+    /// The column is found from where the token lies in memory, so the token
+    /// must be a slice borrowed from `text` (e.g. from `split_whitespace`), not
+    /// a copy of it. Debug builds panic if it isn't.
+    pub fn parse_token(line: usize, text: &str, token: &str, message: impl Into<String>) -> Error {
+        let start = text.as_ptr() as usize;
+        let offset = (token.as_ptr() as usize).wrapping_sub(start);
+        debug_assert!(
+            offset <= text.len() && offset + token.len() <= text.len(),
+            "token '{}' is not a slice of '{}'", token, text,
+        );
+        let column = text.get(..offset).map_or(0, |before| before.chars().count()) + 1;
+        return Error::parse(line, column, message);
+    }
+
+    /// Attach the file a parse error came from and the text of its line
+    pub fn in_file(self, path: &Path, source: &str) -> Error {
+        match self {
+            Error::Parse { line, column, message, .. } => return Error::Parse {
+                file: Some(path.to_path_buf()),
+                line,
+                column,
+                text: source.lines().nth(line.saturating_sub(1)).map(String::from),
+                message,
+            },
+            other => return other,
+        }
+    }
+
+    /// Attach the file a parse error came from and the text of the line it's
+    /// on, for input read a line at a time rather than kept whole
+    pub fn on_line(self, path: &Path, text: &str) -> Error {
+        match self {
+            Error::Parse { line, column, message, .. } => return Error::Parse {
+                file: Some(path.to_path_buf()),
+                line,
+                column,
+                text: Some(text.trim_end_matches(['\r', '\n']).to_string()),
+                message,
+            },
+            other => return other,
+        }
+    }
+
+    /// Build an error for input that breaks the puzzle's rules
+    pub fn puzzle(message: impl Into<String>) -> Error {
+        return Error::Puzzle(message.into());
+    }
+
+    /// The process exit code used to report this kind of error
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Usage(_) => return 2,
+            Error::Io { .. } => return 3,
+            Error::Parse { .. } => return 4,
+            Error::Puzzle(_) => return 5,
+            Error::Panic { .. } => return 6,
+        }
+    }
+
+}
+
+
+impl fmt::Display for Error {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Usage(message) => return write!(f, "{}", message),
+            Error::Io { path, source } => return write!(f, "failed to read {}: {}", path.display(), source),
+            Error::Parse { file, line, column, text, message } => {
+                match file {
+                    Some(file) => write!(f, "parse error at {}:{}:{}: {}", file.display(), line, column, message)?,
+                    None => write!(f, "parse error at line {}, column {}: {}", line, column, message)?,
+                }
+                // Show the offending line with a marker under the column, e.g.
+                //    |
+                // 12 | forward x
+                //    |         ^
+                if let Some(text) = text {
+                    let gutter = " ".repeat(line.to_string().len());
+                    write!(f, "\n{} |\n{} | {}\n{} | {}^", gutter, line, text, gutter, " ".repeat(column.saturating_sub(1)))?;
+                }
+                return Ok(());
+            }
+            Error::Puzzle(message) => return write!(f, "puzzle error: {}", message),
+            Error::Panic { message, location: Some(location) } => return write!(f, "panicked at {}: {}", location, message),
+            Error::Panic { message, location: None } => return write!(f, "panicked: {}", message),
+        }
+    }
+
+}
+
+
+impl std::error::Error for Error {
+
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => return Some(source),
+            _ => return None,
+        }
+    }
+
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_codes_are_distinct() {
+        let errors = [
+            Error::Usage(String::from("usage")),
+            Error::Io { path: PathBuf::from("x"), source: io::Error::from(io::ErrorKind::NotFound) },
+            Error::parse(1, 1, "parse"),
+            Error::puzzle("puzzle"),
+            Error::Panic { message: String::from("panic"), location: None },
+        ];
+        let mut codes: Vec<i32> = errors.iter().map(|e| e.exit_code()).collect();
+        codes.sort();
+        codes.dedup();
+        assert_eq!(codes.len(), errors.len());
+        assert!(!codes.contains(&0));
+        assert!(!codes.contains(&1));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            Error::parse(12, 3, "invalid depth 'x'").to_string(),
+            "parse error at line 12, column 3: invalid depth 'x'",
+        );
+        assert_eq!(Error::puzzle("no board wins").to_string(), "puzzle error: no board wins");
+    }
+
+    #[test]
+    fn test_parse_token() {
+        let text = "forward  fïve";
+        let token = text.split_whitespace().nth(1).unwrap();
+        assert!(matches!(Error::parse_token(2, text, token, "x"), Error::Parse { line: 2, column: 10, .. }));
+        assert!(matches!(Error::parse_token(2, text, text, "x"), Error::Parse { column: 1, .. }));
+        assert!(matches!(Error::parse_token(2, text, &text[text.len()..], "x"), Error::Parse { column: 14, .. }));
+    }
+
+    #[test]
+    #[should_panic(expected = "is not a slice of")]
+    #[cfg(debug_assertions)]
+    fn test_parse_token_copy() {
+        let text = "forward  five";
+        let token = String::from("five");
+        Error::parse_token(1, text, &token, "x");
+    }
+
+    #[test]
+    fn test_display_in_file() {
+        let source = "forward 5\ndown 8\nup fïve\n";
+        let text = source.lines().nth(2).unwrap();
+        let error = Error::parse_token(3, text, &text[3..], "invalid amount 'fïve'");
+        let expected = "\
+parse error at inputs/day02/part1:3:4: invalid amount 'fïve'
+  |
+3 | up fïve
+  |    ^";
+        assert_eq!(error.in_file(Path::new("inputs/day02/part1"), source).to_string(), expected);
+    }
+
+}
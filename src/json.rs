@@ -0,0 +1,110 @@
+//! Just enough JSON to write machine-readable reports
+
+use std::fmt;
+
+
+/// A JSON value
+#[derive(Debug, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(i128),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(&'static str, Json)>),
+}
+
+
+impl From<&str> for Json {
+    fn from(s: &str) -> Json {
+        return Json::String(String::from(s));
+    }
+}
+
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(value: Option<T>) -> Json {
+        return value.map_or(Json::Null, Into::into);
+    }
+}
+
+
+/// Write a string with the quoting and escaping required by JSON
+fn write_string(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+
+    f.write_str("\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    return f.write_str("\"");
+
+}
+
+
+impl fmt::Display for Json {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+
+        match self {
+            Json::Null => return f.write_str("null"),
+            Json::Bool(b) => return write!(f, "{}", b),
+            Json::Number(n) => return write!(f, "{}", n),
+            Json::String(s) => return write_string(f, s),
+            Json::Array(values) => {
+                f.write_str("[")?;
+                for (idx, value) in values.iter().enumerate() {
+                    if idx > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                return f.write_str("]");
+            }
+            Json::Object(members) => {
+                f.write_str("{")?;
+                for (idx, (key, value)) in members.iter().enumerate() {
+                    if idx > 0 {
+                        f.write_str(",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                return f.write_str("}");
+            }
+        }
+
+    }
+
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escaping() {
+        let value = Json::from("a \"quoted\"\\path\nnext\u{1}");
+        assert_eq!(r#""a \"quoted\"\\path\nnext\u0001""#, value.to_string());
+    }
+
+    #[test]
+    fn test_nested() {
+        let value = Json::Object(vec![
+            ("day", Json::Number(1)),
+            ("answers", Json::Array(vec![Json::Number(-7), Json::Null])),
+            ("ok", Json::Bool(true)),
+            ("error", Json::from(None::<&str>)),
+        ]);
+        assert_eq!(r#"{"day":1,"answers":[-7,null],"ok":true,"error":null}"#, value.to_string());
+    }
+
+}
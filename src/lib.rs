@@ -0,0 +1,44 @@
+//! Advent of Code 2021
+//!
+//! Solutions to the 2021 puzzles, one module per day. Every day implements
+//! [`Puzzle`], splitting its work into parsing the input and solving each part
+//! for an [`Answer`]:
+//!
+//! ```
+//! use aoc_2021_sjunot::Puzzle;
+//! use aoc_2021_sjunot::day01::Day01;
+//!
+//! let input = Day01::parse("199\n200\n208\n210\n200\n207\n240\n269\n260\n263").unwrap();
+//! assert_eq!(Day01::part1(&input).unwrap(), "7");
+//! assert_eq!(Day01::part2(&input).unwrap(), "5");
+//! ```
+//!
+//! The completed days are listed in [`registry::DAYS`], which the binary (and
+//! anything else wanting to run every day) dispatches through. [`runner`] and
+//! [`bench`](mod@bench) time a day's phases, [`answers`] checks them against known-good
+//! answers, [`stress`] checks them against brute-force references on random
+//! inputs and [`report`] presents the results as text or JSON.
+
+#![allow(clippy::needless_return)]
+#![allow(clippy::needless_range_loop)]
+
+pub mod answer;
+pub mod answers;
+pub mod bench;
+pub mod error;
+pub mod json;
+pub mod puzzle;
+pub mod registry;
+pub mod report;
+pub mod runner;
+pub mod stress;
+pub mod utils;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+
+pub use answer::Answer;
+pub use error::{Error, Result};
+pub use puzzle::{Example, Puzzle};
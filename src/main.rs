@@ -1,50 +1,707 @@
-use core::panic;
-/// Advent of Code 2021
+//! Advent of Code 2021 - command line interface
+
+#![allow(clippy::needless_return)]
+#![allow(clippy::needless_range_loop)]
 
 use std::env;
-use std::path::Path;
+use std::fs;
+use std::io::{self, BufReader, Read};
+use std::path::{Path, PathBuf};
 use std::process;
+use std::thread;
 
-mod utils;
+mod cli;
+mod scaffold;
+mod watch;
 
-mod day01;
-mod day02;
-mod day03;
-mod day04;
+use aoc_2021_sjunot::{answers, bench, day01, json, registry, report, runner, stress};
+use aoc_2021_sjunot::day01::Day01;
+use aoc_2021_sjunot::{Error, Puzzle};
 
 
-fn main() {
+/// Read the puzzle input for a day, from stdin if the path is "-"
+fn read_input(day: u32, input_path: &Path) -> Result<String, Error> {
 
-    // Arguments required:
-    //  * day (int), e.g. "1"
-    //  * input (path, str), e.g. "./inputs/day01/part1"
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 3 {
-        eprintln!("Invalid number of arguments; please provide the day and path to file containing inputs.");
-        process::exit(1);
-    }
-    let args_day = &args[1];
-    let args_input_path = &args[2];
+    let io_error = |source| Error::Io { path: input_path.to_path_buf(), source };
 
-    // Parse argument:  day, as str->int
-    let day: i32 = args_day.parse::<i32>().expect("'day' argument must be parsable to i32 (e.g. '1')");
-
-    // Parse argument:  path to input, as str->Path
-    let input_path: &Path = Path::new(&args_input_path);
+    if input_path == Path::new(cli::STDIN) {
+        let mut source = String::new();
+        io::stdin().read_to_string(&mut source).map_err(io_error)?;
+        return Ok(source);
+    }
 
     // Validate argument:  path to inputs
     if !input_path.exists() {
-        eprintln!("Path to inputs does not exist!");
-        process::exit(1);
+        return Err(io_error(io::Error::new(io::ErrorKind::NotFound, format!(
+            "no input for day {} (save it there, give its path after the day, or set --inputs or ${})",
+            day, cli::INPUTS_ENV,
+        ))));
+    }
+
+    return fs::read_to_string(input_path).map_err(io_error);
+
+}
+
+
+/// Point a parse error at the input it came from
+fn in_input(e: Error, input_path: &Path, source: &str) -> Error {
+    if input_path == Path::new(cli::STDIN) {
+        return e.in_file(Path::new("<stdin>"), source);
+    }
+    return e.in_file(input_path, source);
+}
+
+
+/// Read a day's input and run its solver, with part 2 over windows of another
+/// size if one is given
+fn solve(day: &registry::Day, input_path: &Path, parts: runner::Parts, window: Option<usize>) -> Result<runner::Solution, Error> {
+    let source = read_input(day.day, input_path)?;
+    return day.solve_with_window(&source, parts, window).map_err(|e| in_input(e, input_path, &source));
+}
+
+
+/// The error for a day that isn't in the table of completed days
+fn unavailable(day: u32) -> Error {
+    return Error::Usage(format!("Day {} is not available; completed days are: {}", day, registry::available()));
+}
+
+
+/// Solve a day against every input file in a directory, optionally checking
+/// the answers against the answers files next to them
+fn run_batch(solver: &registry::Day, dir: &Path, parts: runner::Parts, check: bool, format: cli::Format) -> i32 {
+
+    let batch = match answers::solve_dir(solver, dir, parts, check) {
+        Ok(batch) => batch,
+        Err(e) => {
+            eprintln!("{}", e);
+            return e.exit_code();
+        }
+    };
+
+    if format == cli::Format::Text {
+        println!("Day {}: {}", solver.day, solver.title);
+        println!("Will read inputs from every file in:  {}", dir.display());
+    }
+
+    let mut cells: Vec<Vec<String>> = Vec::new();
+    let mut documents: Vec<json::Json> = Vec::new();
+    for file in batch.iter() {
+        let name = file.path.strip_prefix(dir).unwrap_or(&file.path).display().to_string();
+        match format {
+            cli::Format::Text => cells.extend(report::batch_cells(&name, &file.result, file.checks.as_ref())),
+            cli::Format::Json => documents.push(report::batch_json(solver.day, &file.path, &file.result, file.checks.as_ref())),
+        }
+    }
+    let failures = batch.iter().filter(|file| file.failed()).count();
+    let mismatches: usize = batch.iter().map(|file| file.mismatches()).sum();
+
+    match format {
+        cli::Format::Text => {
+            let header = if check { &report::BATCH_HEADER[..] } else { &report::BATCH_HEADER[..4] };
+            print!("{}", report::render(header, &cells));
+            if failures > 0 {
+                eprintln!("{} of {} inputs failed", failures, batch.len());
+            }
+            if mismatches > 0 {
+                eprintln!("{} answer(s) didn't match the answers files", mismatches);
+            }
+        }
+        cli::Format::Json => println!("{}", json::Json::Object(vec![
+            ("day", json::Json::Number(solver.day.into())),
+            ("inputs", json::Json::Array(documents)),
+            ("failures", json::Json::Number(failures as i128)),
+            ("mismatches", json::Json::Number(mismatches as i128)),
+        ])),
+    }
+
+    return if failures > 0 || mismatches > 0 { 1 } else { 0 };
+
+}
+
+
+/// The exit code for a solution: that of its error, or of the first part that
+/// failed
+fn exit_code(result: &Result<runner::Solution, Error>) -> i32 {
+    match result {
+        Ok(solution) => return solution.error().map_or(0, |e| e.exit_code()),
+        Err(e) => return e.exit_code(),
+    }
+}
+
+
+/// Print one part's answer and how long it took, or why it failed; an answer
+/// over several lines (e.g. ASCII art) starts on a line of its own
+fn print_part(number: u32, part: &Result<runner::Part, Error>) {
+    match part {
+        Ok(part) if part.answer.is_multiline() => {
+            println!("Part {} answer ({:?}):\n{}", number, part.elapsed, part.answer);
+        }
+        Ok(part) => println!("Part {} answer: {} ({:?})", number, part.answer, part.elapsed),
+        Err(e) => eprintln!("Part {} failed: {}", number, e),
+    }
+}
+
+
+/// Solve a single day, printing each phase as it's reported
+fn run_day(day: u32, input: Option<PathBuf>, inputs: &Path, parts: runner::Parts, check: bool, window: Option<usize>, format: cli::Format) -> i32 {
+
+    // Use the day's default input unless one was given
+    let input_path = input.clone().unwrap_or(registry::input_path(inputs, day));
+
+    // A directory holds a batch of inputs
+    match registry::find(day) {
+        Some(_) if input_path.is_dir() && window.is_some() => {
+            let e = Error::Usage(String::from("'--window' needs a single input, not a directory"));
+            eprintln!("{}", e);
+            return e.exit_code();
+        }
+        Some(solver) if input_path.is_dir() => return run_batch(solver, &input_path, parts, check, format),
+        Some(_) if check => {
+            let e = Error::Usage(String::from("'--check' needs a directory of inputs"));
+            eprintln!("{}", e);
+            return e.exit_code();
+        }
+        _ => (),
+    }
+
+    // Look up the day in the table of completed days
+    let result = match registry::find(day) {
+        Some(solver) if window.is_some() && solver.solve_windowed.is_none() => {
+            Err(Error::Usage(format!("Day {} has no window to size", day)))
+        }
+        Some(_) if input.is_some() && input_path != Path::new(cli::STDIN) && !input_path.exists() => {
+            Err(Error::Io { path: input_path.clone(), source: io::Error::new(io::ErrorKind::NotFound, "no such file") })
+        }
+        Some(solver) => {
+            if format == cli::Format::Text {
+                println!("Day {}: {}", solver.day, solver.title);
+                if input_path == Path::new(cli::STDIN) {
+                    println!("Will read inputs from standard input");
+                } else {
+                    println!("Will read inputs from this file:  {}", input_path.display());
+                }
+            }
+            solve(solver, &input_path, parts, window)
+        }
+        None => Err(unavailable(day)),
+    };
+
+    match format {
+        cli::Format::Json => println!("{}", report::json(day, &input_path, &result)),
+        cli::Format::Text => match &result {
+            Ok(solution) => {
+                println!("Parsed input in {:?}", solution.parse);
+                if let Some(part1) = &solution.part1 {
+                    print_part(1, part1);
+                }
+                if let Some(part2) = &solution.part2 {
+                    print_part(2, part2);
+                }
+            }
+            Err(e) => eprintln!("{}", e),
+        },
+    }
+
+    return exit_code(&result);
+
+}
+
+
+/// Solve a single day reading its input a line at a time, for inputs too big to
+/// read whole; only day 1 can be solved this way
+fn run_stream(day: u32, input: Option<PathBuf>, inputs: &Path, parts: runner::Parts, window: Option<usize>, format: cli::Format) -> i32 {
+
+    let input_path = input.unwrap_or(registry::input_path(inputs, day));
+    let window = window.unwrap_or(day01::WINDOW);
+    let result = match registry::find(day) {
+        Some(solver) if solver.day == Day01::DAY => {
+            if format == cli::Format::Text {
+                println!("Day {}: {}", solver.day, solver.title);
+                if input_path == Path::new(cli::STDIN) {
+                    println!("Will stream inputs from standard input");
+                } else {
+                    println!("Will stream inputs from this file:  {}", input_path.display());
+                }
+            }
+            if input_path == Path::new(cli::STDIN) {
+                day01::solve_stream(io::stdin().lock(), Path::new("<stdin>"), parts, window)
+            } else {
+                fs::File::open(&input_path)
+                    .map_err(|source| Error::Io { path: input_path.clone(), source })
+                    .and_then(|file| day01::solve_stream(BufReader::new(file), &input_path, parts, window))
+            }
+        }
+        Some(_) => Err(Error::Usage(format!("Day {} can't be streamed; only day 1 can", day))),
+        None => Err(unavailable(day)),
+    };
+
+    match format {
+        cli::Format::Json => println!("{}", report::json(day, &input_path, &result)),
+        cli::Format::Text => match &result {
+            Ok(solution) => {
+                println!("Streamed input in {:?}", solution.parse);
+                for (number, part) in [(1, &solution.part1), (2, &solution.part2)] {
+                    match part {
+                        Some(Ok(part)) => println!("Part {} answer: {}", number, part.answer),
+                        Some(Err(e)) => eprintln!("Part {} failed: {}", number, e),
+                        None => (),
+                    }
+                }
+            }
+            Err(e) => eprintln!("{}", e),
+        },
+    }
+
+    return exit_code(&result);
+
+}
+
+
+/// Describe how day 1's depths change, and how the sums of their windows do
+fn run_analyze(day: u32, input: Option<PathBuf>, inputs: &Path, window: Option<usize>, format: cli::Format) -> i32 {
+
+    let input_path = input.unwrap_or(registry::input_path(inputs, day));
+    let window = window.unwrap_or(day01::WINDOW);
+    let result = match registry::find(day) {
+        Some(solver) if solver.day == Day01::DAY => read_input(day, &input_path).and_then(
+            |source| runner::isolate(|| day01::analyze_input(&source, window)).map_err(|e| in_input(e, &input_path, &source))
+        ),
+        Some(_) => Err(Error::Usage(format!("Day {} can't be analyzed; only day 1 can", day))),
+        None => Err(unavailable(day)),
+    };
+
+    match (format, &result) {
+        (cli::Format::Json, _) => println!("{}", report::analysis_json(day, &input_path, window, &result)),
+        (cli::Format::Text, Ok(analyses)) => {
+            println!("Day {}: {}", Day01::DAY, Day01::TITLE);
+            print!("{}", report::analysis_table(analyses));
+        }
+        (cli::Format::Text, Err(e)) => eprintln!("{}", e),
+    }
+
+    return result.map_or_else(|e| e.exit_code(), |_| 0);
+
+}
+
+
+/// Solve a single day again every time its input file changes, until
+/// interrupted
+fn run_watch(day: u32, input: Option<PathBuf>, inputs: &Path, parts: runner::Parts) -> i32 {
+
+    let input_path = input.unwrap_or(registry::input_path(inputs, day));
+    if input_path == Path::new(cli::STDIN) {
+        let e = Error::Usage(String::from("Can't watch standard input; give the path of an input file"));
+        eprintln!("{}", e);
+        return e.exit_code();
+    }
+    if registry::find(day).is_none() {
+        let e = unavailable(day);
+        eprintln!("{}", e);
+        return e.exit_code();
+    }
+
+    let mut watcher = watch::Watcher::new(&input_path);
+    loop {
+        if watcher.poll() {
+            print!("{}", watch::CLEAR);
+            println!("Watching {} for changes (Ctrl-C to stop)\n", input_path.display());
+            run_day(day, Some(input_path.clone()), inputs, parts, false, None, cli::Format::Text);
+        }
+        thread::sleep(watch::INTERVAL);
+    }
+
+}
+
+
+/// Solve every completed day against its default input and summarize
+fn run_all(inputs: &Path, parts: runner::Parts, format: cli::Format) -> i32 {
+
+    let mut rows: Vec<report::Row> = Vec::new();
+    let mut documents: Vec<json::Json> = Vec::new();
+    let mut failures = 0;
+    for day in registry::DAYS {
+        let input_path = day.input_path(inputs);
+        let result = solve(day, &input_path, parts, None);
+        if exit_code(&result) != 0 {
+            failures += 1;
+        }
+        match format {
+            cli::Format::Text => rows.extend(report::rows(day.day, &result)),
+            cli::Format::Json => documents.push(report::json(day.day, &input_path, &result)),
+        }
+    }
+
+    match format {
+        cli::Format::Text => {
+            print!("{}", report::table(&rows));
+            if failures > 0 {
+                eprintln!("{} of {} days failed", failures, registry::DAYS.len());
+            }
+        }
+        cli::Format::Json => println!("{}", json::Json::Object(vec![
+            ("days", json::Json::Array(documents)),
+            ("failures", json::Json::Number(failures)),
+        ])),
+    }
+
+    return if failures > 0 { 1 } else { 0 };
+
+}
+
+
+/// Benchmark one or every completed day and compare against the baseline
+fn run_bench(day: Option<u32>, config: bench::Config, baseline_path: &Path, threshold: f64, save: bool, inputs: &Path, format: cli::Format) -> i32 {
+
+    let days: Vec<&registry::Day> = match day {
+        Some(day) => match registry::find(day) {
+            Some(solver) => vec![solver],
+            None => {
+                let e = unavailable(day);
+                eprintln!("{}", e);
+                return e.exit_code();
+            }
+        },
+        None => registry::DAYS.iter().collect(),
+    };
+
+    let mut baseline = match bench::Baseline::load(baseline_path) {
+        Ok(baseline) => baseline,
+        Err(e) => {
+            eprintln!("Failed to load the benchmark baseline: {}", e);
+            return 1;
+        }
+    };
+
+    let mut cells: Vec<Vec<String>> = Vec::new();
+    let mut documents: Vec<json::Json> = Vec::new();
+    let mut results: Vec<(u32, bench::Bench)> = Vec::new();
+    let mut failures = 0;
+    let mut regressions = 0;
+    for solver in days {
+        let input_path = solver.input_path(inputs);
+        let result = read_input(solver.day, &input_path).and_then(
+            |source| (solver.bench)(&source, &config).map_err(|e| in_input(e, &input_path, &source))
+        );
+        match &result {
+            Ok(timings) => {
+                regressions += bench::PHASES.iter().zip(timings.phases.iter()).filter(
+                    |(phase, stats)| {
+                        baseline.get(solver.day, phase).is_some_and(|b| bench::is_regression(b, stats, threshold))
+                    }
+                ).count();
+                cells.extend(report::bench_cells(solver.day, timings, &baseline, threshold));
+            }
+            Err(e) => {
+                failures += 1;
+                cells.push(vec![solver.day.to_string(), String::from("-"), format!("FAILED: {}", e)]);
+            }
+        }
+        if format == cli::Format::Json {
+            documents.push(report::bench_json(solver.day, &input_path, &result, &baseline, threshold));
+        }
+        if let Ok(timings) = result {
+            results.push((solver.day, timings));
+        }
+    }
+
+    match format {
+        cli::Format::Text => print!("{}", report::render(&report::BENCH_HEADER, &cells)),
+        cli::Format::Json => println!("{}", json::Json::Object(vec![
+            ("days", json::Json::Array(documents)),
+            ("failures", json::Json::Number(failures)),
+            ("regressions", json::Json::Number(regressions as i128)),
+        ])),
+    }
+
+    if !save && baseline.entries.is_empty() {
+        eprintln!("No baseline yet at {}; pass --save to store these results as one", baseline_path.display());
+    }
+    if save {
+        for (day, timings) in results.iter() {
+            baseline.record(*day, timings);
+        }
+        if let Err(e) = baseline.save(baseline_path) {
+            eprintln!("Failed to save the benchmark baseline to {}: {}", baseline_path.display(), e);
+            return 1;
+        }
+        eprintln!("Saved benchmark baseline to {}", baseline_path.display());
+    }
+
+    if regressions > 0 {
+        eprintln!("{} phase(s) regressed by more than {:.0}% against {}", regressions, threshold * 100.0, baseline_path.display());
+    }
+    return if failures > 0 || regressions > 0 { 1 } else { 0 };
+
+}
+
+
+/// Check a day's answers to the example in its puzzle description
+fn run_example(day: u32, format: cli::Format) -> i32 {
+
+    let example_path = Path::new("<example>");
+    let result = match registry::find(day) {
+        Some(solver) => {
+            if format == cli::Format::Text {
+                println!("Day {}: {}", solver.day, solver.title);
+                println!("Checking the example from the puzzle description");
+            }
+            answers::check_day(solver, solver.example.input, &solver.example.into()).map_err(
+                |e| e.in_file(example_path, solver.example.input)
+            )
+        }
+        None => Err(unavailable(day)),
+    };
+
+    let mut mismatches = 0;
+    match &result {
+        Ok(checks) => {
+            for (part, check) in checks.iter().enumerate() {
+                let (expected, actual, status) = match check {
+                    answers::Check::Match { answer } => (answer, answer, "ok"),
+                    answers::Check::Unknown { actual } => (actual, actual, "unknown"),
+                    answers::Check::Mismatch { expected, actual } => {
+                        mismatches += 1;
+                        (expected, actual, "WRONG")
+                    }
+                    answers::Check::Failed { expected, error } => {
+                        mismatches += 1;
+                        (expected.as_ref().unwrap_or(error), error, "FAILED")
+                    }
+                };
+                if format == cli::Format::Text {
+                    println!("Part {} answer: {} (expected {}, {})", part + 1, actual, expected, status);
+                }
+            }
+        }
+        Err(e) if format == cli::Format::Text => eprintln!("{}", e),
+        Err(_) => (),
+    }
+    if format == cli::Format::Json {
+        println!("{}", report::verify_json(day, example_path, &result));
+    }
+
+    if mismatches > 0 {
+        return 1;
+    }
+    return result.map_or_else(|e| e.exit_code(), |_| 0);
+
+}
+
+
+/// Verify every completed day against its known-good answers
+fn run_verify(inputs: &Path, format: cli::Format) -> i32 {
+
+    let mut cells: Vec<Vec<String>> = Vec::new();
+    let mut diffs: Vec<String> = Vec::new();
+    let mut documents: Vec<json::Json> = Vec::new();
+    let mut failures = 0;
+    let mut mismatches = 0;
+    for day in registry::DAYS {
+        let input_path = day.input_path(inputs);
+        let result = read_input(day.day, &input_path).and_then(|source| answers::verify(day, &input_path, &source));
+        match &result {
+            Ok(checks) => {
+                for (part, check) in checks.iter().enumerate() {
+                    let part = part + 1;
+                    let (expected, actual, status) = match check {
+                        answers::Check::Match { answer } => (answer.clone(), answer.clone(), "ok"),
+                        answers::Check::Unknown { actual } => (String::from("?"), actual.clone(), "unknown"),
+                        answers::Check::Mismatch { expected, actual } => {
+                            mismatches += 1;
+                            diffs.push(format!("Day {} part {} answer changed:\n- {}\n+ {}", day.day, part, expected, actual));
+                            (expected.clone(), actual.clone(), "CHANGED")
+                        }
+                        answers::Check::Failed { expected, error } => {
+                            mismatches += 1;
+                            (expected.clone().unwrap_or(String::from("?")), format!("FAILED: {}", error), "FAILED")
+                        }
+                    };
+                    cells.push(vec![day.day.to_string(), part.to_string(), expected, actual, status.to_string()]);
+                }
+            }
+            Err(e) => {
+                failures += 1;
+                cells.push(vec![day.day.to_string(), String::from("-"), String::new(), String::new(), format!("FAILED: {}", e)]);
+            }
+        }
+        if format == cli::Format::Json {
+            documents.push(report::verify_json(day.day, &input_path, &result));
+        }
+    }
+
+    match format {
+        cli::Format::Text => {
+            print!("{}", report::render(&["Day", "Part", "Expected", "Actual", "Status"], &cells));
+            for diff in diffs.iter() {
+                println!("\n{}", diff);
+            }
+        }
+        cli::Format::Json => println!("{}", json::Json::Object(vec![
+            ("days", json::Json::Array(documents)),
+            ("failures", json::Json::Number(failures)),
+            ("mismatches", json::Json::Number(mismatches)),
+        ])),
+    }
+
+    if failures > 0 || mismatches > 0 {
+        eprintln!("Verification failed: {} changed answer(s), {} failed day(s)", mismatches, failures);
+        return 1;
+    }
+    return 0;
+
+}
+
+
+/// Print a random input for a day
+fn run_generate(day: u32, seed: u64, size: usize) -> i32 {
+    match registry::find(day) {
+        Some(solver) => {
+            print!("{}", (solver.generate)(seed, size));
+            return 0;
+        }
+        None => {
+            let e = unavailable(day);
+            eprintln!("{}", e);
+            return e.exit_code();
+        }
+    }
+}
+
+
+/// Check one or every completed day against brute force on random inputs
+fn run_stress(day: Option<u32>, config: stress::Config, format: cli::Format) -> i32 {
+
+    let days: Vec<&registry::Day> = match day {
+        Some(day) => match registry::find(day) {
+            Some(solver) => vec![solver],
+            None => {
+                let e = unavailable(day);
+                eprintln!("{}", e);
+                return e.exit_code();
+            }
+        },
+        None => registry::DAYS.iter().collect(),
+    };
+
+    let mut cells: Vec<Vec<String>> = Vec::new();
+    let mut documents: Vec<json::Json> = Vec::new();
+    let mut failures = 0;
+    for solver in days {
+        let result = (solver.stress)(&config);
+        let (status, seed, input) = match &result {
+            Ok(cases) => (format!("ok ({} inputs)", cases), None, None),
+            Err(failure) => {
+                failures += 1;
+                (format!("FAILED: {}", failure), Some(failure.seed), Some(failure.input.as_str()))
+            }
+        };
+        match format {
+            cli::Format::Text => cells.push(vec![solver.day.to_string(), status]),
+            cli::Format::Json => documents.push(json::Json::Object(vec![
+                ("day", json::Json::Number(solver.day.into())),
+                ("error", json::Json::from(result.as_ref().err().map(|f| f.message.as_str()))),
+                ("seed", json::Json::from(seed.map(|s| json::Json::Number(s.into())))),
+                ("input", json::Json::from(input)),
+            ])),
+        }
+        if let (cli::Format::Text, Err(failure)) = (format, &result) {
+            eprintln!("Day {} failed; print its input with: aoc-2021-sjunot generate {} --seed {} --size {}",
+                solver.day, solver.day, failure.seed, config.size);
+        }
     }
 
+    match format {
+        cli::Format::Text => print!("{}", report::render(&["Day", "Status"], &cells)),
+        cli::Format::Json => println!("{}", json::Json::Object(vec![
+            ("seed", json::Json::Number(config.seed.into())),
+            ("cases", json::Json::Number(config.cases.into())),
+            ("size", json::Json::Number(config.size as i128)),
+            ("days", json::Json::Array(documents)),
+            ("failures", json::Json::Number(failures)),
+        ])),
+    }
+
+    return if failures > 0 { 1 } else { 0 };
+
+}
+
+
+/// Generate a new day in the crate rooted at the current directory
+fn run_new(day: u32, title: Option<String>, inputs: &Path) -> i32 {
+
+    if !Path::new("src").join("registry.rs").exists() {
+        let e = Error::Usage(String::from("'new' must be run from the root of the crate"));
+        eprintln!("{}", e);
+        return e.exit_code();
+    }
+
+    match scaffold::new_day(Path::new(""), inputs, day, title.as_deref().unwrap_or("TODO")) {
+        Ok(created) => {
+            for path in created.iter() {
+                println!("Wrote {}", path.display());
+            }
+            println!("Save the puzzle input as {}", registry::input_path(inputs, day).display());
+            return 0;
+        }
+        Err(message) => {
+            let e = Error::Usage(format!("Can't add day {}: {}", day, message));
+            eprintln!("{}", e);
+            return e.exit_code();
+        }
+    }
+
+}
+
+
+fn main() {
+
+    // Arguments required, either:
+    //  * day (int), e.g. "1", and optionally input (path, str), e.g. "./inputs/day01/part1", a
+    //    directory of inputs (optionally with "--check"), or "-" for stdin
+    //  * day (int) and "--example"
+    //  * "watch", a day (int) and optionally an input (path, str)
+    //  * "all"
+    //  * "bench" and optionally a day (int)
+    //  * "verify"
+    //  * "generate" and a day (int), or "stress" and optionally a day (int)
+    //  * "new" and a day (int), optionally followed by its title
+    // Optionally:
+    //  * "--json" to print a JSON document instead of text
+    //  * "--inputs" to read default inputs from another directory
+    //  * "--part" to solve only part 1 or part 2
+    //  * benchmark options, see cli::USAGE
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args = match cli::parse(&args) {
+        Ok(args) => args,
+        Err(e) => {
+            let e = Error::Usage(e);
+            eprintln!("{}\n\n{}", e, cli::USAGE);
+            process::exit(e.exit_code());
+        }
+    };
+
+    // Default inputs come from the --inputs option, then the environment
+    let inputs: PathBuf = args.inputs
+        .or(env::var_os(cli::INPUTS_ENV).map(PathBuf::from))
+        .unwrap_or(PathBuf::from(cli::DEFAULT_INPUTS));
+
     // Act on arguments
-    match day {
-        1 => day01::main(input_path),
-        2 => day02::main(input_path),
-        3 => day03::main(input_path),
-        4 => day04::main(input_path),
-        _ => panic!("BUG: missing day (TODO: create a table of completed days)"),
+    let code = match args.command {
+        cli::Command::Day { day, input, parts, check, window } => run_day(day, input, &inputs, parts, check, window, args.format),
+        cli::Command::Watch { day, input, parts } => run_watch(day, input, &inputs, parts),
+        cli::Command::Example { day } => run_example(day, args.format),
+        cli::Command::Stream { day, input, parts, window } => run_stream(day, input, &inputs, parts, window, args.format),
+        cli::Command::Analyze { day, input, window } => run_analyze(day, input, &inputs, window, args.format),
+        cli::Command::All { parts } => run_all(&inputs, parts, args.format),
+        cli::Command::Verify => run_verify(&inputs, args.format),
+        cli::Command::Generate { day, seed, size } => run_generate(day, seed, size),
+        cli::Command::Stress { day, config } => run_stress(day, config, args.format),
+        cli::Command::New { day, title } => run_new(day, title, &inputs),
+        cli::Command::Bench { day, warmup, iterations, baseline, threshold, save } => {
+            let config = bench::Config { warmup, iterations };
+            run_bench(day, config, &baseline, threshold, save, &inputs, args.format)
+        }
     };
+    process::exit(code);
 
-}
\ No newline at end of file
+}
@@ -0,0 +1,47 @@
+//! The shape shared by every day's solution
+//!
+//! Each day splits its work into the same three phases: parse the puzzle input
+//! once, then solve part 1 and part 2 from the parsed input. Solutions return
+//! their [`Answer`]s (or an error) rather than printing them, leaving reporting
+//! to the runner. Each day also carries the worked example from its puzzle
+//! description, which both its unit tests and `--example` run against.
+
+use super::answer::Answer;
+use super::error::Result;
+
+
+/// The example input given in a puzzle's description and its answers, written
+/// as the answers print
+#[derive(Debug, Clone, Copy)]
+pub struct Example {
+    pub input: &'static str,
+    pub part1: &'static str,
+    pub part2: &'static str,
+}
+
+
+/// A day's puzzle
+pub trait Puzzle {
+
+    /// Day of the advent calendar, e.g. 1
+    const DAY: u32;
+
+    /// Title of the puzzle as given by AoC, e.g. "Sonar Sweep"
+    const TITLE: &'static str;
+
+    /// The example from the puzzle description
+    const EXAMPLE: Example;
+
+    /// The puzzle input after parsing, shared by both parts
+    type Input;
+
+    /// Parse the raw contents of an input file
+    fn parse(source: &str) -> Result<Self::Input>;
+
+    /// Solve part 1
+    fn part1(input: &Self::Input) -> Result<Answer>;
+
+    /// Solve part 2
+    fn part2(input: &Self::Input) -> Result<Answer>;
+
+}
@@ -0,0 +1,146 @@
+//! The table of completed days
+//!
+//! Every solved puzzle is registered here exactly once. The binary dispatches
+//! through this table, so adding a day only requires a new entry below.
+
+use std::path::{Path, PathBuf};
+
+use super::bench::{self, Bench};
+use super::error::{Error, Result};
+use super::puzzle::Example;
+use super::runner::{self, Parts, Solution};
+use super::stress::{self, Stress};
+
+use super::day01;
+use super::day02;
+use super::day03;
+use super::day04;
+
+
+/// Solve a day with part 2 over windows of the given size
+pub type SolveWindowed = fn(&str, Parts, usize) -> Result<Solution>;
+
+
+/// A completed day and how to run it
+pub struct Day {
+    pub day: u32,
+    pub title: &'static str,
+    pub example: Example,
+    pub solve: fn(&str, Parts) -> Result<Solution>,
+    /// Solve with part 2 over windows of another size, for the days whose part
+    /// 2 slides a window over the input
+    pub solve_windowed: Option<SolveWindowed>,
+    pub bench: fn(&str, &bench::Config) -> Result<Bench>,
+    pub generate: fn(u64, usize) -> String,
+    pub stress: fn(&stress::Config) -> std::result::Result<u32, stress::Failure>,
+}
+
+
+impl Day {
+
+    /// Build the table entry for a puzzle from its implementation
+    pub const fn of<P: Stress>() -> Day {
+        return Day {
+            day: P::DAY,
+            title: P::TITLE,
+            example: P::EXAMPLE,
+            solve: runner::solve::<P>,
+            solve_windowed: None,
+            bench: bench::bench::<P>,
+            generate: stress::generate::<P>,
+            stress: stress::stress::<P>,
+        };
+    }
+
+    /// Solve the day, with part 2 over windows of the given size if there is
+    /// one; only days with `solve_windowed` can take one
+    pub fn solve_with_window(&self, source: &str, parts: Parts, window: Option<usize>) -> Result<Solution> {
+        match (window, self.solve_windowed) {
+            (None, _) => return (self.solve)(source, parts),
+            (Some(window), Some(solve_windowed)) => return solve_windowed(source, parts, window),
+            (Some(_), None) => return Err(Error::Usage(format!("Day {} has no window to size", self.day))),
+        }
+    }
+
+    /// Where the day's puzzle input is kept within a directory of inputs
+    pub fn input_path(&self, inputs: &Path) -> PathBuf {
+        return input_path(inputs, self.day);
+    }
+
+}
+
+
+/// All completed days, in ascending order
+pub const DAYS: &[Day] = &[
+    Day { solve_windowed: Some(day01::solve_windowed), ..Day::of::<day01::Day01>() },
+    Day::of::<day02::Day02>(),
+    Day::of::<day03::Day03>(),
+    Day::of::<day04::Day04>(),
+];
+
+
+/// Where a day's puzzle input is kept within a directory of inputs, e.g.
+/// "inputs/day01/part1"
+pub fn input_path(inputs: &Path, day: u32) -> PathBuf {
+    return inputs.join(format!("day{:02}", day)).join("part1");
+}
+
+
+/// Look up a completed day by its number
+pub fn find(day: u32) -> Option<&'static Day> {
+    return DAYS.iter().find(|d| d.day == day);
+}
+
+
+/// Comma separated list of the completed days, e.g. "1, 2, 3"
+pub fn available() -> String {
+    return DAYS.iter()
+        .map(|d| d.day.to_string())
+        .collect::<Vec<String>>()
+        .join(", ");
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_days_are_unique_and_sorted() {
+        for pair in DAYS.windows(2) {
+            assert!(pair[0].day < pair[1].day);
+        }
+    }
+
+    #[test]
+    fn test_find() {
+        assert_eq!(find(4).map(|d| d.title), Some("Giant Squid"));
+        assert!(find(0).is_none());
+        assert!(find(25).is_none());
+    }
+
+    #[test]
+    fn test_examples() {
+        // A newly generated day has no example until it's copied in
+        for day in DAYS.iter().filter(|d| !d.example.input.is_empty()) {
+            let solution = (day.solve)(day.example.input, Parts::Both).unwrap();
+            assert_eq!(solution.part1.map(|p| p.unwrap().answer.to_string()).as_deref(), Some(day.example.part1), "day {} part 1", day.day);
+            assert_eq!(solution.part2.map(|p| p.unwrap().answer.to_string()).as_deref(), Some(day.example.part2), "day {} part 2", day.day);
+        }
+    }
+
+    #[test]
+    fn test_solve_with_window() {
+        let source = find(1).unwrap().example.input;
+        let solution = find(1).unwrap().solve_with_window(source, Parts::Part2, Some(1)).unwrap();
+        assert_eq!(solution.part2.map(|p| p.unwrap().answer), Some(7.into()));
+        assert!(matches!(find(2).unwrap().solve_with_window("", Parts::Both, Some(1)), Err(Error::Usage(_))));
+    }
+
+    #[test]
+    fn test_input_path() {
+        let day = find(1).unwrap();
+        assert_eq!(day.input_path(Path::new("inputs")), PathBuf::from("inputs/day01/part1"));
+    }
+
+}
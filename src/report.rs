@@ -0,0 +1,502 @@
+//! Present the results of running puzzles
+
+use std::path::Path;
+use std::time::Duration;
+
+use super::answers::Check;
+use super::bench::{self, Baseline, Bench};
+use super::day01::{Analyses, Analysis, Step};
+use super::error::Error;
+use super::json::Json;
+use super::runner::{Part, Solution};
+
+
+/// One line of the summary table
+pub struct Row {
+    pub day: u32,
+    pub part: &'static str,
+    pub answer: String,
+    pub elapsed: Option<Duration>,
+}
+
+
+/// How a failure reads in a table; only the first line of the error fits
+fn failed(e: &Error) -> String {
+    return format!("FAILED: {}", e.to_string().lines().next().unwrap_or_default());
+}
+
+
+/// Rows for a day's solution (or its failure) in the summary table; a part that
+/// failed has a row of its own
+pub fn rows(day: u32, result: &Result<Solution, Error>) -> Vec<Row> {
+
+    match result {
+        Ok(solution) => {
+            let mut rows = vec![
+                Row { day, part: "parse", answer: String::new(), elapsed: Some(solution.parse) },
+            ];
+            for (part, solved) in [("1", &solution.part1), ("2", &solution.part2)] {
+                match solved {
+                    Some(Ok(solved)) => rows.push(
+                        Row { day, part, answer: solved.answer.inline(), elapsed: Some(solved.elapsed) }
+                    ),
+                    Some(Err(e)) => rows.push(Row { day, part, answer: failed(e), elapsed: None }),
+                    None => (),
+                }
+            }
+            return rows;
+        }
+        Err(e) => return vec![Row { day, part: "-", answer: failed(e), elapsed: None }],
+    }
+
+}
+
+
+/// Render cells as an aligned plain text table under a header
+pub fn render(header: &[&str], cells: &[Vec<String>]) -> String {
+
+    let mut widths: Vec<usize> = header.iter().map(|h| h.chars().count()).collect();
+    for line in cells.iter() {
+        for (width, cell) in widths.iter_mut().zip(line.iter()) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut output = String::new();
+    let header: Vec<String> = header.iter().map(|h| h.to_string()).collect();
+    for line in std::iter::once(&header).chain(cells.iter()) {
+        let padded: Vec<String> = line.iter().zip(widths.iter()).map(
+            |(cell, width)| format!("{:<width$}", cell, width = width)
+        ).collect();
+        output.push_str(padded.join("  ").trim_end());
+        output.push('\n');
+    }
+
+    return output;
+
+}
+
+
+/// Render rows as an aligned plain text table
+pub fn table(rows: &[Row]) -> String {
+
+    let cells: Vec<Vec<String>> = rows.iter().map(
+        |row| {
+            vec![
+                row.day.to_string(),
+                row.part.to_string(),
+                row.answer.clone(),
+                row.elapsed.map(|e| format!("{:?}", e)).unwrap_or_default(),
+            ]
+        }
+    ).collect();
+
+    return render(&["Day", "Part", "Answer", "Time"], &cells);
+
+}
+
+
+/// Header of the table for a batch of inputs; the status column is only shown
+/// when answers are checked
+pub const BATCH_HEADER: [&str; 5] = ["File", "Part", "Answer", "Time", "Status"];
+
+
+/// How a checked answer compares with the expected one
+fn status(check: &Check) -> String {
+    match check {
+        Check::Match { .. } => return String::from("ok"),
+        Check::Unknown { .. } => return String::from("unknown"),
+        Check::Mismatch { expected, .. } => return format!("WRONG (expected {})", expected),
+        Check::Failed { expected: Some(expected), .. } => return format!("FAILED (expected {})", expected),
+        Check::Failed { expected: None, .. } => return String::from("FAILED"),
+    }
+}
+
+
+/// Lines of the batch table for one input file, with the status of each
+/// part's answer if answers are checked
+pub fn batch_cells(file: &str, result: &Result<Solution, Error>, checks: Option<&[Check; 2]>) -> Vec<Vec<String>> {
+
+    return rows(0, result).into_iter().map(
+        |row| {
+            let mut line = vec![
+                file.to_string(),
+                row.part.to_string(),
+                row.answer,
+                row.elapsed.map(|e| format!("{:?}", e)).unwrap_or_default(),
+            ];
+            if let Some([check1, check2]) = checks {
+                line.push(match row.part {
+                    "1" => status(check1),
+                    "2" => status(check2),
+                    _ => String::new(),
+                });
+            }
+            return line;
+        }
+    ).collect();
+
+}
+
+
+/// Header of the benchmark table
+pub const BENCH_HEADER: [&str; 8] = ["Day", "Phase", "Min", "Median", "Mean", "Std dev", "Baseline", "Change"];
+
+
+/// Lines of the benchmark table for a day, compared against a baseline
+pub fn bench_cells(day: u32, timings: &Bench, baseline: &Baseline, threshold: f64) -> Vec<Vec<String>> {
+
+    let mut cells: Vec<Vec<String>> = Vec::new();
+    for (phase, stats) in bench::PHASES.iter().zip(timings.phases.iter()) {
+        let (before, change) = match baseline.get(day, phase) {
+            Some(before) => {
+                let flag = if bench::is_regression(before, stats, threshold) { "  REGRESSION" } else { "" };
+                (
+                    format!("{:?}", before.median),
+                    format!("{:+.1}%{}", bench::change(before, stats) * 100.0, flag),
+                )
+            }
+            None => (String::from("-"), String::from("-")),
+        };
+        cells.push(vec![
+            day.to_string(),
+            phase.to_string(),
+            format!("{:?}", stats.min),
+            format!("{:?}", stats.median),
+            format!("{:?}", stats.mean),
+            format!("{:?}", stats.stddev),
+            before,
+            change,
+        ]);
+    }
+    return cells;
+
+}
+
+
+/// A duration in whole nanoseconds
+fn nanos(duration: Duration) -> Json {
+    return Json::Number(duration.as_nanos() as i128);
+}
+
+
+/// A part that was solved, unless it failed or wasn't asked for
+fn solved(part: &Option<Result<Part, Error>>) -> Option<&Part> {
+    return part.as_ref().and_then(|p| p.as_ref().ok());
+}
+
+
+/// A day's solution (or its failure) as a JSON document
+pub fn json(day: u32, input: &Path, result: &Result<Solution, Error>) -> Json {
+
+    // A part that failed has no answer; the error of the first one that did is
+    // the solution's error
+    let (part1, part2, timings, error) = match result {
+        Ok(solution) => {
+            let answer = |part: &Option<Result<Part, Error>>| solved(part).map_or(Json::Null, |p| Json::from(&p.answer));
+            let elapsed = |part: &Option<Result<Part, Error>>| solved(part).map_or(Json::Null, |p| nanos(p.elapsed));
+            (
+                answer(&solution.part1),
+                answer(&solution.part2),
+                Json::Object(vec![
+                    ("parse", nanos(solution.parse)),
+                    ("part1", elapsed(&solution.part1)),
+                    ("part2", elapsed(&solution.part2)),
+                ]),
+                solution.error().map_or(Json::Null, |e| Json::from(e.to_string().as_str())),
+            )
+        }
+        Err(e) => (Json::Null, Json::Null, Json::Null, Json::from(e.to_string().as_str())),
+    };
+
+    return Json::Object(vec![
+        ("day", Json::Number(day.into())),
+        ("input", Json::from(input.display().to_string().as_str())),
+        ("part1", part1),
+        ("part2", part2),
+        ("timings_ns", timings),
+        ("error", error),
+    ]);
+
+}
+
+
+/// A day's benchmark (or its failure) as a JSON document
+pub fn bench_json(day: u32, input: &Path, result: &Result<Bench, Error>, baseline: &Baseline, threshold: f64) -> Json {
+
+    let (phases, error) = match result {
+        Ok(timings) => {
+            let phases = bench::PHASES.iter().zip(timings.phases.iter()).map(
+                |(phase, stats)| {
+                    let before = baseline.get(day, phase);
+                    (*phase, Json::Object(vec![
+                        ("min_ns", nanos(stats.min)),
+                        ("median_ns", nanos(stats.median)),
+                        ("mean_ns", nanos(stats.mean)),
+                        ("stddev_ns", nanos(stats.stddev)),
+                        ("baseline_median_ns", before.map_or(Json::Null, |b| nanos(b.median))),
+                        ("regression", Json::Bool(before.is_some_and(|b| bench::is_regression(b, stats, threshold)))),
+                    ]))
+                }
+            ).collect();
+            (Json::Object(phases), Json::Null)
+        }
+        Err(e) => (Json::Null, Json::from(e.to_string().as_str())),
+    };
+
+    return Json::Object(vec![
+        ("day", Json::Number(day.into())),
+        ("input", Json::from(input.display().to_string().as_str())),
+        ("phases", phases),
+        ("error", error),
+    ]);
+
+}
+
+
+/// One input file's solution as a JSON document, with the check of each
+/// part's answer if answers are checked
+pub fn batch_json(day: u32, input: &Path, result: &Result<Solution, Error>, checks: Option<&[Check; 2]>) -> Json {
+    let mut document = json(day, input, result);
+    if let (Json::Object(fields), Some([check1, check2])) = (&mut document, checks) {
+        fields.push(("checks", Json::Object(vec![("part1", check_json(check1)), ("part2", check_json(check2))])));
+    }
+    return document;
+}
+
+
+/// The result of checking one part's answer as a JSON document
+fn check_json(check: &Check) -> Json {
+    let (expected, actual, status, error) = match check {
+        Check::Match { answer } => (Some(answer.as_str()), Some(answer.as_str()), "ok", None),
+        Check::Unknown { actual } => (None, Some(actual.as_str()), "unknown", None),
+        Check::Mismatch { expected, actual } => (Some(expected.as_str()), Some(actual.as_str()), "changed", None),
+        Check::Failed { expected, error } => (expected.as_deref(), None, "failed", Some(error.as_str())),
+    };
+    return Json::Object(vec![
+        ("status", Json::from(status)),
+        ("expected", Json::from(expected)),
+        ("actual", Json::from(actual)),
+        ("error", Json::from(error)),
+    ]);
+}
+
+
+/// A day's verification (or its failure) as a JSON document
+pub fn verify_json(day: u32, input: &Path, result: &Result<[Check; 2], Error>) -> Json {
+
+    let (part1, part2, error) = match result {
+        Ok([check1, check2]) => (check_json(check1), check_json(check2), Json::Null),
+        Err(e) => (Json::Null, Json::Null, Json::from(e.to_string().as_str())),
+    };
+
+    return Json::Object(vec![
+        ("day", Json::Number(day.into())),
+        ("input", Json::from(input.display().to_string().as_str())),
+        ("part1", part1),
+        ("part2", part2),
+        ("error", error),
+    ]);
+
+}
+
+
+/// What each row of an analysis table shows
+const ANALYSIS_ROWS: [&str; 7] = [
+    "Values", "Increases", "Decreases", "Unchanged", "Longest increasing run", "Largest rise", "Largest drop",
+];
+
+
+/// The analysis of one series as a column of a table, in the order of
+/// `ANALYSIS_ROWS`
+fn series_column(analysis: &Analysis) -> Vec<String> {
+    let step = |step: Option<Step>| step.map_or(String::from("-"), |s| format!("{:+} (at {})", s.change, s.index));
+    let run = analysis.longest_run;
+    return vec![
+        analysis.values.to_string(),
+        analysis.increases.to_string(),
+        analysis.decreases.to_string(),
+        analysis.unchanged.to_string(),
+        format!("{} ({} to {})", run.length(), run.start, run.end),
+        step(analysis.largest_rise),
+        step(analysis.largest_drop),
+    ];
+}
+
+
+/// Day 1's analyses as a table with a column for the depths and one for their
+/// windows, followed by where each of them increases
+pub fn analysis_table(analyses: &Analyses) -> String {
+
+    let windows_name = format!("Windows of {}", analyses.window);
+    let window_column = analyses.windows.as_ref().map_or(vec![String::from("-"); ANALYSIS_ROWS.len()], series_column);
+    let cells: Vec<Vec<String>> = ANALYSIS_ROWS.iter().zip(series_column(&analyses.depths)).zip(window_column).map(
+        |((row, depths), windows)| vec![row.to_string(), depths, windows]
+    ).collect();
+    let mut output = render(&["", "Depths", &windows_name], &cells);
+
+    let positions = |analysis: &Analysis| {
+        analysis.increase_positions.iter().map(|i| i.to_string()).collect::<Vec<String>>().join(" ")
+    };
+    output.push_str(&format!("\nDepths increase at: {}\n", positions(&analyses.depths)));
+    match &analyses.windows {
+        Ok(windows) => output.push_str(&format!("{} increase at: {}\n", windows_name, positions(windows))),
+        Err(e) => output.push_str(&format!("{}: {}\n", windows_name, e)),
+    }
+    return output;
+
+}
+
+
+/// The analysis of one series as a JSON document
+fn series_json(analysis: &Analysis) -> Json {
+    let step = |step: Option<Step>| Json::from(step.map(|s| Json::Object(vec![
+        ("index", Json::Number(s.index as i128)),
+        ("change", Json::Number(s.change.into())),
+    ])));
+    let run = analysis.longest_run;
+    return Json::Object(vec![
+        ("values", Json::Number(analysis.values as i128)),
+        ("increases", Json::Number(analysis.increases as i128)),
+        ("decreases", Json::Number(analysis.decreases as i128)),
+        ("unchanged", Json::Number(analysis.unchanged as i128)),
+        ("longest_run", Json::Object(vec![
+            ("start", Json::Number(run.start as i128)),
+            ("end", Json::Number(run.end as i128)),
+            ("length", Json::Number(run.length() as i128)),
+        ])),
+        ("largest_drop", step(analysis.largest_drop)),
+        ("largest_rise", step(analysis.largest_rise)),
+        ("increase_positions", Json::Array(
+            analysis.increase_positions.iter().map(|&i| Json::Number(i as i128)).collect()
+        )),
+    ]);
+}
+
+
+/// Day 1's analyses (or their failure) as a JSON document
+pub fn analysis_json(day: u32, input: &Path, window: usize, result: &Result<Analyses, Error>) -> Json {
+
+    let (depths, windows, error) = match result {
+        Ok(analyses) => (
+            series_json(&analyses.depths),
+            match &analyses.windows {
+                Ok(windows) => series_json(windows),
+                Err(e) => Json::Object(vec![("error", Json::from(e.to_string().as_str()))]),
+            },
+            Json::Null,
+        ),
+        Err(e) => (Json::Null, Json::Null, Json::from(e.to_string().as_str())),
+    };
+
+    return Json::Object(vec![
+        ("day", Json::Number(day.into())),
+        ("input", Json::from(input.display().to_string().as_str())),
+        ("window", Json::Number(window as i128)),
+        ("depths", depths),
+        ("windows", windows),
+        ("error", error),
+    ]);
+
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::answers::{check_solution, Expected};
+    use super::super::day01::Day01;
+    use super::super::runner::{self, Parts};
+
+    #[test]
+    fn test_table_alignment() {
+        let rows = vec![
+            Row { day: 1, part: "1", answer: String::from("1655"), elapsed: Some(Duration::from_micros(5)) },
+            Row { day: 12, part: "-", answer: String::from("FAILED: oops"), elapsed: None },
+        ];
+        let expected = "\
+Day  Part  Answer        Time
+1    1     1655          5µs
+12   -     FAILED: oops
+";
+        assert_eq!(expected, table(&rows));
+    }
+
+    #[test]
+    fn test_batch_cells() {
+        let solution: Result<Solution, Error> = Ok(Solution {
+            parse: Duration::from_micros(2),
+            part1: Some(Ok(Part { answer: 7.into(), elapsed: Duration::from_micros(1) })),
+            part2: None,
+        });
+        let checks = [
+            Check::Mismatch { expected: String::from("5"), actual: String::from("7") },
+            Check::Unknown { actual: String::new() },
+        ];
+        assert_eq!(batch_cells("alice", &solution, Some(&checks)), vec![
+            vec!["alice", "parse", "", "2µs", ""],
+            vec!["alice", "1", "7", "1µs", "WRONG (expected 5)"],
+        ]);
+        assert_eq!(batch_cells("alice", &solution, None)[1], vec!["alice", "1", "7", "1µs"]);
+
+        // A part that failed says so, unlike one that wasn't asked for
+        let solution = runner::solve::<Day01>("199\n200", Parts::Both);
+        let expected = Expected { part1: Some(String::from("1")), part2: Some(String::from("0")) };
+        let checks = check_solution(solution.as_ref().unwrap(), &expected);
+        let cells = batch_cells("bob", &solution, Some(&checks));
+        assert_eq!(cells[1], vec!["bob", "1", "1", &cells[1][3], "ok"]);
+        assert_eq!(cells[2][4], "FAILED (expected 0)");
+        assert!(cells[2][2].starts_with("FAILED: puzzle error: "), "{}", cells[2][2]);
+        let document = check_json(&checks[1]).to_string();
+        assert!(document.starts_with(r#"{"status":"failed","expected":"0","actual":null,"error":"puzzle error: "#), "{}", document);
+    }
+
+    #[test]
+    fn test_analysis_table() {
+        let analyses = super::super::day01::analyze_input("1\n3\n2\n", 2).unwrap();
+        // The header's first cell is empty, so its line starts with spaces
+        let expected = concat!(
+            "                        Depths      Windows of 2\n",
+            "Values                  3           2\n",
+            "Increases               1           1\n",
+            "Decreases               1           0\n",
+            "Unchanged               0           0\n",
+            "Longest increasing run  2 (0 to 1)  2 (0 to 1)\n",
+            "Largest rise            +2 (at 1)   +1 (at 1)\n",
+            "Largest drop            -1 (at 2)   -\n",
+            "\n",
+            "Depths increase at: 1\n",
+            "Windows of 2 increase at: 1\n",
+        );
+        assert_eq!(expected, analysis_table(&analyses));
+
+        let analyses = super::super::day01::analyze_input("1\n3\n2\n", 4).unwrap();
+        let document = analysis_json(1, Path::new("-"), 4, &Ok(analyses)).to_string();
+        assert!(document.contains(r#""windows":{"error":"puzzle error: "#), "{}", document);
+    }
+
+    #[test]
+    fn test_part_failure_keeps_other_part() {
+        // Two depths answer part 1, but part 2 needs a window's worth
+        let result = runner::solve::<Day01>("199\n200", Parts::Both);
+        let answers: Vec<(&str, String)> = rows(1, &result).into_iter().map(|row| (row.part, row.answer)).collect();
+        assert_eq!(answers[1], ("1", String::from("1")));
+        assert_eq!(answers[2].0, "2");
+        assert!(answers[2].1.starts_with("FAILED: puzzle error: "), "{}", answers[2].1);
+
+        let document = json(1, Path::new("-"), &result).to_string();
+        assert!(document.contains(r#""part1":1,"part2":null,"#), "{}", document);
+        assert!(document.contains(r#""error":"puzzle error: "#), "{}", document);
+    }
+
+    #[test]
+    fn test_json_failure() {
+        let result: Result<Solution, Error> = Err(Error::puzzle("no board ever wins"));
+        let document = json(2, Path::new("inputs/day02/part1"), &result);
+        assert_eq!(
+            r#"{"day":2,"input":"inputs/day02/part1","part1":null,"part2":null,"timings_ns":null,"error":"puzzle error: no board ever wins"}"#,
+            document.to_string(),
+        );
+    }
+
+}
@@ -0,0 +1,245 @@
+//! Run a puzzle's phases and time each of them
+//!
+//! Solvers run in isolation: a panic is caught and returned as
+//! `Error::Panic`, so one broken day doesn't take down a whole batch.
+
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::panic::{self, AssertUnwindSafe, PanicHookInfo};
+use std::sync::Once;
+use std::time::{Duration, Instant};
+
+use super::answer::Answer;
+use super::error::{Error, Result};
+use super::puzzle::Puzzle;
+
+
+/// The answer to one part of a puzzle and how long it took to compute
+pub struct Part {
+    pub answer: Answer,
+    pub elapsed: Duration,
+}
+
+
+/// Which parts of a puzzle to solve
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Parts {
+    Part1,
+    Part2,
+    Both,
+}
+
+
+impl Parts {
+
+    pub fn part1(self) -> bool {
+        return self != Parts::Part2;
+    }
+
+    pub fn part2(self) -> bool {
+        return self != Parts::Part1;
+    }
+
+}
+
+
+/// The outcome of running the phases of a puzzle against one input; parts
+/// that weren't asked for are None. Each part succeeds or fails on its own,
+/// e.g. part 1 can be answered when part 2 needs more input than it's given.
+pub struct Solution {
+    pub parse: Duration,
+    pub part1: Option<Result<Part>>,
+    pub part2: Option<Result<Part>>,
+}
+
+
+impl Solution {
+
+    /// The error of the first part that failed, if any did
+    pub fn error(&self) -> Option<&Error> {
+        return [&self.part1, &self.part2].into_iter().flatten().find_map(|part| part.as_ref().err());
+    }
+
+}
+
+
+thread_local! {
+
+    /// Whether this thread is running a solver under `isolate`
+    static ISOLATED: Cell<bool> = const { Cell::new(false) };
+
+    /// Where the last isolated panic on this thread happened
+    static LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+
+}
+
+
+static HOOK: Once = Once::new();
+
+
+/// Install a panic hook that quietly records the location of isolated panics
+/// and leaves every other panic to the hook that was there before
+fn install_hook() {
+    HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info: &PanicHookInfo| {
+            if ISOLATED.with(|isolated| isolated.get()) {
+                let location = info.location().map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()));
+                LOCATION.with(|last| *last.borrow_mut() = location);
+            } else {
+                previous(info);
+            }
+        }));
+    });
+}
+
+
+/// The message a panic was raised with
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        return message.to_string();
+    }
+    if let Some(message) = payload.downcast_ref::<String>() {
+        return message.clone();
+    }
+    return String::from("(no message)");
+}
+
+
+/// Run part of a solver, turning a panic into `Error::Panic`
+pub fn isolate<T>(run: impl FnOnce() -> Result<T>) -> Result<T> {
+
+    install_hook();
+    let was_isolated = ISOLATED.with(|isolated| isolated.replace(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(run));
+    ISOLATED.with(|isolated| isolated.set(was_isolated));
+
+    return result.unwrap_or_else(|payload| Err(Error::Panic {
+        message: panic_message(payload.as_ref()),
+        location: LOCATION.with(|last| last.borrow_mut().take()),
+    }));
+
+}
+
+
+/// Time one part of a puzzle
+fn timed(solve: impl FnOnce() -> Result<Answer>) -> Result<Part> {
+    let start = Instant::now();
+    let answer = solve()?;
+    return Ok(Part { answer, elapsed: start.elapsed() });
+}
+
+
+/// Parse the input and solve the requested parts of the puzzle, timing each
+/// phase. Only a failure to parse fails the whole solution.
+pub fn solve<P: Puzzle>(source: &str, parts: Parts) -> Result<Solution> {
+    return solve_with(source, parts, P::parse, P::part1, P::part2);
+}
+
+
+/// Like `solve`, for phases that aren't a `Puzzle`'s, e.g. a part solved with
+/// different settings than the puzzle's own
+pub fn solve_with<I>(
+    source: &str,
+    parts: Parts,
+    parse: impl FnOnce(&str) -> Result<I>,
+    part1: impl FnOnce(&I) -> Result<Answer>,
+    part2: impl FnOnce(&I) -> Result<Answer>,
+) -> Result<Solution> {
+
+    let start = Instant::now();
+    let input = isolate(|| parse(source))?;
+    let parse = start.elapsed();
+
+    let part1 = parts.part1().then(|| isolate(|| timed(|| part1(&input))));
+    let part2 = parts.part2().then(|| isolate(|| timed(|| part2(&input))));
+
+    return Ok(Solution { parse, part1, part2 });
+
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::day01::Day01;
+    use super::super::puzzle::Example;
+
+    /// A puzzle whose part 2 always panics
+    struct Panicky;
+
+    impl Puzzle for Panicky {
+        const DAY: u32 = 0;
+        const TITLE: &'static str = "Panicky";
+        const EXAMPLE: Example = Example { input: "", part1: "1", part2: "2" };
+        type Input = Vec<u32>;
+
+        fn parse(_source: &str) -> Result<Vec<u32>> {
+            return Ok(Vec::new());
+        }
+
+        fn part1(_input: &Vec<u32>) -> Result<Answer> {
+            return Ok(1.into());
+        }
+
+        fn part2(input: &Vec<u32>) -> Result<Answer> {
+            return Ok(input[0].into());
+        }
+    }
+
+    /// The answer to a part that was solved
+    fn answer(part: Option<Result<Part>>) -> Option<Answer> {
+        return part.map(|p| p.unwrap().answer);
+    }
+
+    #[test]
+    fn test_solve_selected_parts() {
+        let source = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263";
+
+        let solution = solve::<Day01>(source, Parts::Both).unwrap();
+        assert_eq!(answer(solution.part1), Some(7.into()));
+        assert_eq!(answer(solution.part2), Some(5.into()));
+
+        let solution = solve::<Day01>(source, Parts::Part2).unwrap();
+        assert!(solution.part1.is_none());
+        assert_eq!(answer(solution.part2), Some(5.into()));
+    }
+
+    #[test]
+    fn test_parts_fail_on_their_own() {
+        // Part 2 needs at least three depths; part 1 is still answered
+        let solution = solve::<Day01>("199\n200", Parts::Both).unwrap();
+        assert!(matches!(solution.error(), Some(Error::Puzzle(_))));
+        assert!(matches!(solution.part2, Some(Err(Error::Puzzle(_)))));
+        assert_eq!(answer(solution.part1), Some(1.into()));
+
+        let solution = solve::<Day01>("199\n200", Parts::Part1).unwrap();
+        assert!(solution.error().is_none());
+        assert!(solution.part2.is_none());
+
+        assert!(matches!(solve::<Day01>("199\nx", Parts::Both), Err(Error::Parse { .. })));
+    }
+
+    #[test]
+    fn test_panics_are_isolated() {
+
+        assert!(solve::<Panicky>("", Parts::Part1).unwrap().error().is_none());
+        let solution = solve::<Panicky>("", Parts::Both).unwrap();
+        assert_eq!(answer(solution.part1), Some(1.into()));
+        match solution.part2 {
+            Some(Err(Error::Panic { message, location: Some(location) })) => {
+                assert!(message.contains("index out of bounds"), "{}", message);
+                assert!(location.starts_with("src/runner.rs:"), "{}", location);
+            }
+            _ => panic!("expected the panic to be caught"),
+        }
+
+        let nested: Result<()> = isolate(|| {
+            assert!(isolate(|| -> Result<()> { panic!("inner") }).is_err());
+            panic!("outer {}", 2)
+        });
+        assert!(matches!(nested, Err(Error::Panic { message, .. }) if message == "outer 2"));
+
+    }
+
+}
@@ -0,0 +1,291 @@
+//! Generating the skeleton of a new day
+//!
+//! `new <day>` writes src/dayNN.rs from the template below, registers it in
+//! src/lib.rs and the table in src/registry.rs, and creates its input
+//! directory. Nothing is written if the day already exists.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+
+/// The module written for a new day; `__DAY__`, `__NN__` and `__TITLE__` are
+/// replaced by the day number, the zero-padded day number and the title
+const TEMPLATE: &str = r#"//! AoC 2021 - Day __DAY__: __TITLE__
+//!
+//! TODO: summarize the puzzle.
+
+use super::answer::Answer;
+use super::error::{Error, Result};
+use super::puzzle::{Example, Puzzle};
+use super::stress::Stress;
+use super::utils::Rng;
+
+
+/// The puzzle input
+pub struct Input {
+    pub lines: Vec<String>,
+}
+
+
+/// TODO: name part 1
+pub mod part1 {
+
+    use super::*;
+
+    /// Parse the puzzle input
+    pub fn input(source: &str) -> Result<Input> {
+        let lines: Vec<String> = source.lines().map(String::from).collect();
+        if lines.is_empty() {
+            return Err(Error::parse(1, 1, "expected at least one line"));
+        }
+        return Ok(Input { lines });
+    }
+
+    /// TODO: describe the answer to part 1
+    pub fn solution(input: &Input) -> Result<Answer> {
+        return Err(Error::puzzle(format!("part 1 isn't solved yet ({} lines of input)", input.lines.len())));
+    }
+
+}
+
+
+/// TODO: name part 2
+pub mod part2 {
+
+    use super::*;
+
+    /// TODO: describe the answer to part 2
+    pub fn solution(input: &Input) -> Result<Answer> {
+        return Err(Error::puzzle(format!("part 2 isn't solved yet ({} lines of input)", input.lines.len())));
+    }
+
+}
+
+
+/// __TITLE__
+pub struct Day__NN__;
+
+impl Puzzle for Day__NN__ {
+
+    const DAY: u32 = __DAY__;
+    const TITLE: &'static str = "__TITLE__";
+
+    // TODO: copy the example and its answers from the puzzle description
+    const EXAMPLE: Example = Example {
+        input: "",
+        part1: "",
+        part2: "",
+    };
+
+    type Input = Input;
+
+    fn parse(source: &str) -> Result<Input> {
+        return part1::input(source);
+    }
+
+    fn part1(input: &Input) -> Result<Answer> {
+        return part1::solution(input);
+    }
+
+    fn part2(input: &Input) -> Result<Answer> {
+        return part2::solution(input);
+    }
+
+}
+
+
+impl Stress for Day__NN__ {
+
+    /// TODO: generate a random, valid input of the given size
+    fn generate(_rng: &mut Rng, _size: usize) -> String {
+        return String::new();
+    }
+
+    fn reference1(_input: &Input) -> Result<Answer> {
+        return Err(Error::puzzle("part 1 has no reference solution yet"));
+    }
+
+    fn reference2(_input: &Input) -> Result<Answer> {
+        return Err(Error::puzzle("part 2 has no reference solution yet"));
+    }
+
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The example inputs and outputs given by AOC
+    #[test]
+    #[ignore = "TODO: fill in the example from the puzzle description"]
+    fn test_part1_example() {
+
+        let part1_input = part1::input(Day__NN__::EXAMPLE.input).unwrap();
+        let part1_output = part1::solution(&part1_input).unwrap();
+        assert_eq!(part1_output, Day__NN__::EXAMPLE.part1);
+        let part2_output = part2::solution(&part1_input).unwrap();
+        assert_eq!(part2_output, Day__NN__::EXAMPLE.part2);
+    }
+
+}
+"#;
+
+
+/// The source of a new day's module
+pub fn render(day: u32, title: &str) -> String {
+    return TEMPLATE
+        .replace("__NN__", &format!("{:02}", day))
+        .replace("__DAY__", &day.to_string())
+        .replace("__TITLE__", title);
+}
+
+
+/// Insert `line` among the lines for which `day_of` gives a day number, keeping
+/// them in ascending order. Fails if the day is already there or there are no
+/// such lines to insert among.
+fn insert_day(source: &str, day: u32, line: &str, day_of: impl Fn(&str) -> Option<u32>) -> Result<String, String> {
+
+    let lines: Vec<&str> = source.lines().collect();
+    let days: Vec<(usize, u32)> = lines.iter().enumerate().filter_map(
+        |(idx, l)| day_of(l.trim()).map(|d| (idx, d))
+    ).collect();
+
+    if days.iter().any(|(_, d)| *d == day) {
+        return Err(format!("day {} is already registered", day));
+    }
+    let (last, _) = days.last().ok_or(format!("found nowhere to register day {}", day))?;
+
+    // Before the first later day, or after the last one
+    let at = days.iter().find(|(_, d)| *d > day).map_or(last + 1, |(idx, _)| *idx);
+    let first = lines[days[0].0];
+    let indent = &first[..first.len() - first.trim_start().len()];
+
+    let mut out: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+    out.insert(at, format!("{}{}", indent, line));
+    return Ok(out.join("\n") + "\n");
+
+}
+
+
+/// The day number in e.g. "day04" followed by `suffix`
+fn day_between(text: &str, prefix: &str, suffix: &str) -> Option<u32> {
+    let rest = text.strip_prefix(prefix)?.strip_prefix("day")?;
+    return rest.strip_suffix(suffix)?.parse().ok();
+}
+
+
+/// Add "pub mod dayNN;" to the source of src/lib.rs
+pub fn register_module(lib: &str, day: u32) -> Result<String, String> {
+    return insert_day(lib, day, &format!("pub mod day{:02};", day), |l| day_between(l, "pub mod ", ";"));
+}
+
+
+/// Add the import and table entry for a day to the source of src/registry.rs
+pub fn register_day(registry: &str, day: u32) -> Result<String, String> {
+    let registry = insert_day(registry, day, &format!("use super::day{:02};", day), |l| day_between(l, "use super::", ";"))?;
+    return insert_day(
+        &registry, day, &format!("Day::of::<day{0:02}::Day{0:02}>(),", day),
+        // Anywhere in the line, as an entry may override some of its fields
+        |l| day_between(l.split_once("Day::of::<")?.1.split("::").next()?, "", ""),
+    );
+}
+
+
+/// Generate a new day within the crate at `root`, returning the files and
+/// directories created
+pub fn new_day(root: &Path, inputs: &Path, day: u32, title: &str) -> Result<Vec<PathBuf>, String> {
+
+    if !(1..=25).contains(&day) {
+        return Err(format!("'day' must be between 1 and 25, got {}", day));
+    }
+
+    let module_path = root.join("src").join(format!("day{:02}.rs", day));
+    let lib_path = root.join("src").join("lib.rs");
+    let registry_path = root.join("src").join("registry.rs");
+    let read = |path: &Path| fs::read_to_string(path).map_err(|e| format!("failed to read {}: {}", path.display(), e));
+    let write = |path: &Path, contents: &str| fs::write(path, contents).map_err(|e| format!("failed to write {}: {}", path.display(), e));
+
+    // Refuse to touch anything unless every step can succeed
+    if module_path.exists() {
+        return Err(format!("{} already exists", module_path.display()));
+    }
+    let lib = register_module(&read(&lib_path)?, day)?;
+    let registry = register_day(&read(&registry_path)?, day)?;
+
+    write(&module_path, &render(day, title))?;
+    write(&lib_path, &lib)?;
+    write(&registry_path, &registry)?;
+
+    let input_dir = inputs.join(format!("day{:02}", day));
+    match fs::create_dir_all(&input_dir) {
+        Ok(()) => (),
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => (),
+        Err(e) => return Err(format!("failed to create {}: {}", input_dir.display(), e)),
+    }
+
+    return Ok(vec![module_path, lib_path, registry_path, input_dir]);
+
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let source = render(5, "Hydrothermal Venture");
+        assert!(source.starts_with("//! AoC 2021 - Day 5: Hydrothermal Venture\n"));
+        assert!(source.contains("pub struct Day05;"));
+        assert!(source.contains("const DAY: u32 = 5;"));
+        assert!(!source.contains("__"));
+    }
+
+    #[test]
+    fn test_register_module() {
+        let lib = "pub mod utils;\n\npub mod day01;\npub mod day04;\n\npub use error::Error;\n";
+        assert_eq!(
+            register_module(lib, 2).unwrap(),
+            "pub mod utils;\n\npub mod day01;\npub mod day02;\npub mod day04;\n\npub use error::Error;\n",
+        );
+        assert_eq!(
+            register_module(lib, 12).unwrap(),
+            "pub mod utils;\n\npub mod day01;\npub mod day04;\npub mod day12;\n\npub use error::Error;\n",
+        );
+        assert!(register_module(lib, 4).is_err());
+        assert!(register_module("pub mod utils;\n", 4).is_err());
+    }
+
+    #[test]
+    fn test_register_day() {
+        let registry = "\
+use super::day01;
+
+pub const DAYS: &[Day] = &[
+    Day::of::<day01::Day01>(),
+];
+";
+        assert_eq!(register_day(registry, 2).unwrap(), "\
+use super::day01;
+use super::day02;
+
+pub const DAYS: &[Day] = &[
+    Day::of::<day01::Day01>(),
+    Day::of::<day02::Day02>(),
+];
+");
+        assert!(register_day(registry, 1).is_err());
+    }
+
+    #[test]
+    fn test_register_day_in_registry() {
+        let registry = fs::read_to_string("src/registry.rs").unwrap();
+        assert_eq!(register_day(&registry, 1), Err(String::from("day 1 is already registered")));
+        let registered = register_day(&registry, 25).unwrap();
+        assert!(registered.contains("use super::day25;\n"));
+        assert!(registered.contains("),\n    Day::of::<day25::Day25>(),\n];"));
+    }
+
+}
@@ -0,0 +1,145 @@
+//! Differential testing against random inputs
+//!
+//! Each day can generate random puzzle inputs that follow the rules of its
+//! puzzle, and solve them again with a simple brute-force reference. Stress
+//! testing runs both over many generated inputs and reports the first input
+//! they disagree on, along with the seed that reproduces it.
+
+use std::fmt;
+
+use super::answer::Answer;
+use super::error::Result;
+use super::puzzle::Puzzle;
+use super::runner::isolate;
+use super::utils::Rng;
+
+
+/// A day that can generate its own inputs and check its own answers
+pub trait Stress: Puzzle {
+
+    /// A random, valid input of the given size, e.g. the number of lines or
+    /// boards
+    fn generate(rng: &mut Rng, size: usize) -> String;
+
+    /// Solve part 1 the slow, obvious way
+    fn reference1(input: &Self::Input) -> Result<Answer>;
+
+    /// Solve part 2 the slow, obvious way
+    fn reference2(input: &Self::Input) -> Result<Answer>;
+
+}
+
+
+/// How many inputs to generate and how big they are
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Config {
+    pub seed: u64,
+    pub cases: u32,
+    pub size: usize,
+}
+
+
+impl Default for Config {
+    fn default() -> Config {
+        return Config { seed: 2021, cases: 1000, size: 100 };
+    }
+}
+
+
+/// A generated input the solver and the reference disagree on
+#[derive(Debug)]
+pub struct Failure {
+
+    /// Seed that generates this input again (with the same size)
+    pub seed: u64,
+
+    pub input: String,
+    pub message: String,
+
+}
+
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(f, "{} (seed {})", self.message, self.seed);
+    }
+}
+
+
+/// The seed for one case of a run, so that a failing case can be generated
+/// again on its own
+fn case_seed(config: &Config, case: u32) -> u64 {
+    return config.seed.wrapping_add(case.into());
+}
+
+
+/// Generate a single input
+pub fn generate<P: Stress>(seed: u64, size: usize) -> String {
+    return P::generate(&mut Rng::new(seed), size);
+}
+
+
+/// Describe a solver's or the reference's outcome for a failure message
+fn outcome(result: &Result<Answer>) -> String {
+    match result {
+        Ok(answer) => return answer.inline(),
+        Err(e) => return format!("error ({})", e),
+    }
+}
+
+
+/// Check the solver against the reference on generated inputs, returning
+/// how many inputs were checked
+pub fn stress<P: Stress>(config: &Config) -> std::result::Result<u32, Failure> {
+
+    for case in 0..config.cases {
+        let seed = case_seed(config, case);
+        let source = isolate(|| Ok(generate::<P>(seed, config.size))).map_err(
+            |e| Failure { seed, input: String::new(), message: format!("generator {}", e) }
+        )?;
+        let fail = |message: String| Failure { seed, input: source.clone(), message };
+
+        let input = isolate(|| P::parse(&source)).map_err(|e| fail(format!("generated input doesn't parse: {}", e)))?;
+        let parts = [
+            (1, isolate(|| P::part1(&input)), isolate(|| P::reference1(&input))),
+            (2, isolate(|| P::part2(&input)), isolate(|| P::reference2(&input))),
+        ];
+        for (part, actual, expected) in parts.iter() {
+            match (actual, expected) {
+                (Ok(actual), Ok(expected)) if actual == expected => (),
+                _ => return Err(fail(format!(
+                    "part {}: solver gave {}, reference gave {}", part, outcome(actual), outcome(expected),
+                ))),
+            }
+        }
+    }
+
+    return Ok(config.cases);
+
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::registry;
+
+    #[test]
+    fn test_days_agree_with_references() {
+        let config = Config { seed: 1, cases: 200, size: 20 };
+        // A newly generated day has nothing to check until it's solved
+        for day in registry::DAYS.iter().filter(|d| !d.example.input.is_empty()) {
+            if let Err(failure) = (day.stress)(&config) {
+                panic!("day {}: {}\n{}", day.day, failure, failure.input);
+            }
+        }
+    }
+
+    #[test]
+    fn test_generate_is_repeatable() {
+        for day in registry::DAYS {
+            assert_eq!((day.generate)(7, 10), (day.generate)(7, 10));
+        }
+    }
+
+}
@@ -1,5 +1,10 @@
-/// Highly reusable snippets of code
+//! Highly reusable snippets of code
 
+use std::fmt;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use super::error::{Error, Result};
 
 /// Remove excess whitespace from a hardcoded multiline string. These strings
 /// may have an extra empty line at the top and bottom and tabs or spaces
@@ -8,6 +13,8 @@
 /// Example:
 ///
 /// ```
+/// use aoc_2021_sjunot::utils::remove_multiline_whitespace;
+///
 /// let x = "
 ///     a
 ///     b
@@ -15,7 +22,6 @@
 /// assert_eq!("a\nb", remove_multiline_whitespace(x));
 /// ```
 ///
-#[allow(dead_code)]
 pub fn remove_multiline_whitespace(source: &str) -> String {
 
     let lines: Vec<&str> = source.trim().lines().collect();
@@ -25,10 +31,8 @@ pub fn remove_multiline_whitespace(source: &str) -> String {
     for line_idx in 0..num_lines {
         let line = lines[line_idx];
         let line_stripped = line.trim();
-        if line_idx == 0 || line_idx == num_lines - 1 {
-            if line_stripped.is_empty() {
-                continue;
-            }
+        if (line_idx == 0 || line_idx == num_lines - 1) && line_stripped.is_empty() {
+            continue;
         }
         lines_stripped.push(line_stripped);
     }
@@ -38,10 +42,484 @@ pub fn remove_multiline_whitespace(source: &str) -> String {
 }
 
 
+/// A small, seedable pseudo-random number generator (SplitMix64) for
+/// generating puzzle inputs. Not suitable for anything needing real
+/// randomness.
+///
+/// Example:
+///
+/// ```
+/// use aoc_2021_sjunot::utils::Rng;
+///
+/// let mut a = Rng::new(7);
+/// let mut b = Rng::new(7);
+/// assert_eq!(a.next_u64(), b.next_u64());
+/// assert!((1..=6).contains(&a.between(1, 6)));
+/// ```
+pub struct Rng {
+    state: u64,
+}
+
+
+impl Rng {
+
+    /// Start a sequence; the same seed always gives the same sequence
+    pub fn new(seed: u64) -> Rng {
+        return Rng { state: seed };
+    }
+
+    /// The next number in the sequence
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        return z ^ (z >> 31);
+    }
+
+    /// A number from 0 up to but excluding `n`, which must not be 0
+    pub fn below(&mut self, n: usize) -> usize {
+        return (self.next_u64() % n as u64) as usize;
+    }
+
+    /// A number from `low` up to and including `high`
+    pub fn between(&mut self, low: i64, high: i64) -> i64 {
+        let span = (high - low) as u64 + 1;
+        return low + (self.next_u64() % span) as i64;
+    }
+
+    /// Put the items in a random order
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+
+}
+
+
+/// A rectangular grid of cells, e.g. a bingo board or a map, stored row by
+/// row. Positions are (row, column) pairs counting from the top left.
+///
+/// Example:
+///
+/// ```
+/// use aoc_2021_sjunot::utils::Grid;
+///
+/// let grid = Grid::parse_words("1 2 3\n4 5 16", 1, |n| n.parse::<u32>().map_err(|e| e.to_string())).unwrap();
+/// assert_eq!((grid.width(), grid.height()), (3, 2));
+/// assert_eq!(grid[(1, 0)], 4);
+/// assert_eq!(grid.column(2).unwrap().sum::<u32>(), 19);
+/// assert_eq!(grid.transpose().to_string(), " 1  4\n 2  5\n 3 16\n");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+
+/// Offsets of the neighbours sharing an edge with a cell, in reading order
+const NEIGHBOURS_4: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+
+/// Offsets of the neighbours sharing an edge or a corner with a cell, in
+/// reading order
+const NEIGHBOURS_8: [(isize, isize); 8] = [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)];
+
+
+impl<T> Grid<T> {
+
+    /// Build a grid from its rows; None if they aren't all the same width, or
+    /// are empty, since a grid with rows but no columns has no cells to hold them
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Grid<T>> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.len());
+        if rows.iter().any(|row| row.len() != width) || (width == 0 && height > 0) {
+            return None;
+        }
+        return Some(Grid { width, height, cells: rows.into_iter().flatten().collect() });
+    }
+
+    pub fn width(&self) -> usize {
+        return self.width;
+    }
+
+    pub fn height(&self) -> usize {
+        return self.height;
+    }
+
+    /// The cell at a position, if it's within the grid
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        if row >= self.height || col >= self.width {
+            return None;
+        }
+        return self.cells.get(row * self.width + col);
+    }
+
+    /// The cell at a position, if it's within the grid, for changing
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+        if row >= self.height || col >= self.width {
+            return None;
+        }
+        return self.cells.get_mut(row * self.width + col);
+    }
+
+    /// The cells of one row, left to right, if it's within the grid
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        if row >= self.height {
+            return None;
+        }
+        return Some(&self.cells[row * self.width..(row + 1) * self.width]);
+    }
+
+    /// Every row, top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        return self.cells.chunks(self.width.max(1));
+    }
+
+    /// The cells of one column, top to bottom, if it's within the grid
+    pub fn column(&self, col: usize) -> Option<impl Iterator<Item = &T>> {
+        if col >= self.width {
+            return None;
+        }
+        return Some(self.cells.iter().skip(col).step_by(self.width));
+    }
+
+    /// Every column, left to right
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        return (0..self.width).map(|col| self.cells.iter().skip(col).step_by(self.width));
+    }
+
+    /// Every cell, row by row
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        return self.cells.iter();
+    }
+
+    /// Every cell with its position, row by row
+    pub fn positions(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        return self.cells.iter().enumerate().map(|(i, cell)| ((i / self.width, i % self.width), cell));
+    }
+
+    /// The positions within the grid that are offset from a position
+    fn offsets<'a>(&self, row: usize, col: usize, offsets: &'a [(isize, isize)]) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let (width, height) = (self.width, self.height);
+        return offsets.iter().filter_map(move |&(d_row, d_col)| {
+            let neighbour = (row.checked_add_signed(d_row)?, col.checked_add_signed(d_col)?);
+            return (neighbour.0 < height && neighbour.1 < width).then_some(neighbour);
+        });
+    }
+
+    /// Positions of the (up to 4) cells sharing an edge with a position
+    pub fn neighbours4(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> {
+        return self.offsets(row, col, &NEIGHBOURS_4);
+    }
+
+    /// Positions of the (up to 8) cells sharing an edge or a corner with a
+    /// position
+    pub fn neighbours8(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> {
+        return self.offsets(row, col, &NEIGHBOURS_8);
+    }
+
+    /// A grid of the same shape with every cell converted
+    pub fn map<U>(&self, convert: impl FnMut(&T) -> U) -> Grid<U> {
+        return Grid { width: self.width, height: self.height, cells: self.cells.iter().map(convert).collect() };
+    }
+
+    /// The grid flipped over its diagonal, so rows become columns
+    pub fn transpose(&self) -> Grid<T> where T: Clone {
+        let cells = self.columns().flatten().cloned().collect();
+        return Grid { width: self.height, height: self.width, cells };
+    }
+
+    /// Parse rows of cells, one row per line, using `split` to divide a line
+    /// into the text of its cells. Blank lines are skipped.
+    fn parse_rows<'a>(
+        source: &'a str,
+        first_line: usize,
+        split: impl Fn(&'a str) -> Vec<&'a str>,
+        cell: impl Fn(&str) -> std::result::Result<T, String>,
+    ) -> Result<Grid<T>> {
+
+        let mut width: Option<usize> = None;
+        let mut height = 0;
+        let mut cells: Vec<T> = Vec::new();
+        for (line_idx, line) in source.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let line_number = first_line + line_idx;
+            let tokens = split(line);
+            let expected = *width.get_or_insert(tokens.len());
+            // Point at the first extra cell, or just past the end of a short row
+            let message = format!("expected {} cells per row, got {}", expected, tokens.len());
+            if tokens.len() > expected {
+                return Err(Error::parse_token(line_number, line, tokens[expected], message));
+            }
+            if tokens.len() < expected {
+                return Err(Error::parse(line_number, line.trim_end().chars().count() + 1, message));
+            }
+            for token in tokens {
+                cells.push(cell(token).map_err(|message| Error::parse_token(line_number, line, token, message))?);
+            }
+            height += 1;
+        }
+
+        return Ok(Grid { width: width.unwrap_or(0), height, cells });
+
+    }
+
+    /// Parse a grid with a cell per whitespace separated word, e.g. a bingo
+    /// board. Lines count from `first_line` in errors; blank lines are skipped
+    /// and no lines at all is an empty grid.
+    pub fn parse_words(source: &str, first_line: usize, cell: impl Fn(&str) -> std::result::Result<T, String>) -> Result<Grid<T>> {
+        return Grid::parse_rows(source, first_line, |line| line.split_whitespace().collect(), cell);
+    }
+
+    /// Parse a grid with a cell per character, e.g. a map. Lines count from
+    /// `first_line` in errors; blank lines are skipped and no lines at all is
+    /// an empty grid.
+    pub fn parse_chars(source: &str, first_line: usize, cell: impl Fn(char) -> std::result::Result<T, String>) -> Result<Grid<T>> {
+        return Grid::parse_rows(
+            source,
+            first_line,
+            |line| line.trim_end().char_indices().map(|(i, c)| &line[i..i + c.len_utf8()]).collect(),
+            |text| cell(text.chars().next().expect("BUG: cells are one character")),
+        );
+    }
+
+}
+
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        return self.get(row, col).unwrap_or_else(
+            || panic!("({}, {}) is outside a {}x{} grid", row, col, self.height, self.width)
+        );
+    }
+
+}
+
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        return self.get_mut(row, col).unwrap_or_else(
+            || panic!("({}, {}) is outside a {}x{} grid", row, col, height, width)
+        );
+    }
+
+}
+
+
+/// One line per row. Single character cells are printed side by side, e.g. a
+/// map; wider cells are right aligned and separated by a space, e.g. a bingo
+/// board.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+
+        let texts: Vec<String> = self.cells.iter().map(|cell| cell.to_string()).collect();
+        let width = texts.iter().map(|t| t.chars().count()).max().unwrap_or(0);
+        let separator = if width > 1 { " " } else { "" };
+
+        for row in texts.chunks(self.width.max(1)) {
+            let padded: Vec<String> = row.iter().map(|t| format!("{:>width$}", t, width = width)).collect();
+            writeln!(f, "{}", padded.join(separator))?;
+        }
+        return Ok(());
+
+    }
+
+}
+
+
+// Parsers for the shapes puzzle inputs usually come in. Lines count from 1 and
+// errors point at the offending text; `what` names a value in error messages,
+// e.g. "invalid depth 'x'".
+
+
+/// Parse a token, which must be a slice of the text of the given line
+///
+/// Example:
+///
+/// ```
+/// use aoc_2021_sjunot::utils::token;
+///
+/// let line = "forward 5";
+/// assert_eq!(token::<i32>(1, line, &line[8..], "amount").unwrap(), 5);
+/// assert!(token::<i32>(1, line, &line[..7], "amount").is_err());
+/// ```
+pub fn token<T: FromStr>(line_number: usize, line: &str, token: &str, what: &str) -> Result<T> {
+    return token.parse().map_err(
+        |_| Error::parse_token(line_number, line, token, format!("invalid {} '{}'", what, token))
+    );
+}
+
+
+/// Parse one value per line, ignoring whitespace around it and blank lines
+///
+/// Example:
+///
+/// ```
+/// use aoc_2021_sjunot::utils::numbers_per_line;
+///
+/// assert_eq!(numbers_per_line::<i32>("199\n200 \n\n 208\r\n", "depth").unwrap(), vec![199, 200, 208]);
+/// ```
+pub fn numbers_per_line<T: FromStr>(source: &str, what: &str) -> Result<Vec<T>> {
+    return source.lines().enumerate().filter_map(
+        |(line_idx, line)| number_on_line(line_idx + 1, line, what).transpose()
+    ).collect();
+}
+
+
+/// Parse the value on a line, ignoring whitespace around it, or None for a
+/// blank line; the line by line version of `numbers_per_line`, for input
+/// that's read a line at a time
+pub fn number_on_line<T: FromStr>(line_number: usize, line: &str, what: &str) -> Result<Option<T>> {
+    let text = line.trim();
+    if text.is_empty() {
+        return Ok(None);
+    }
+    return token(line_number, line, text, what).map(Some);
+}
+
+
+/// Split the input into blocks separated by blank lines, each with the line it
+/// starts on. Any number of blank lines separate two blocks.
+///
+/// Example:
+///
+/// ```
+/// use aoc_2021_sjunot::utils::blocks;
+///
+/// assert_eq!(blocks("1,2\n\n3 4\n5 6\n\n\n7 8\n"), vec![(1, "1,2"), (3, "3 4\n5 6"), (7, "7 8")]);
+/// ```
+pub fn blocks(source: &str) -> Vec<(usize, &str)> {
+
+    let mut blocks: Vec<(usize, &str)> = Vec::new();
+    let mut start: Option<(usize, usize)> = None;  // line number and offset of the current block
+    let mut offset = 0;
+    for (line_idx, line) in source.split_inclusive('\n').enumerate() {
+        let blank = line.trim().is_empty();
+        match start {
+            None if !blank => start = Some((line_idx + 1, offset)),
+            Some((line_number, begin)) if blank => {
+                blocks.push((line_number, source[begin..offset].trim_end_matches(['\r', '\n'])));
+                start = None;
+            }
+            _ => (),
+        }
+        offset += line.len();
+    }
+    if let Some((line_number, begin)) = start {
+        blocks.push((line_number, source[begin..].trim_end_matches(['\r', '\n'])));
+    }
+
+    return blocks;
+
+}
+
+
+/// Parse a comma separated list of values on one line, e.g. "7,4,9"
+///
+/// Example:
+///
+/// ```
+/// use aoc_2021_sjunot::utils::comma_separated;
+///
+/// assert_eq!(comma_separated::<u32>(1, "7, 4,9", "number").unwrap(), vec![7, 4, 9]);
+/// ```
+pub fn comma_separated<T: FromStr>(line_number: usize, line: &str, what: &str) -> Result<Vec<T>> {
+    return line.split(',').map(|value| token(line_number, line, value.trim(), what)).collect();
+}
+
+
+/// Parse a matrix of whitespace separated values, one row per line, starting
+/// on the given line of the input
+///
+/// Example:
+///
+/// ```
+/// use aoc_2021_sjunot::utils::matrix;
+///
+/// let grid = matrix::<u32>("22 13\n 8  2", 3, "number").unwrap();
+/// assert_eq!(grid.row(1), Some(&[8, 2][..]));
+/// ```
+pub fn matrix<T: FromStr>(source: &str, first_line: usize, what: &str) -> Result<Grid<T>> {
+    return Grid::parse_words(
+        source, first_line, |value| value.parse().map_err(|_| format!("invalid {} '{}'", what, value)),
+    );
+}
+
+
+/// Parse lines of a keyword followed by a value, e.g. "forward 5". `keyword`
+/// recognizes the keywords; `names` are what to call the keyword and the
+/// value in error messages. Blank lines are skipped, as `numbers_per_line`
+/// skips them.
+///
+/// Example:
+///
+/// ```
+/// use aoc_2021_sjunot::utils::keyword_values;
+///
+/// let keyword = |k: &str| if k == "up" || k == "down" { Some(k.len()) } else { None };
+/// assert_eq!(keyword_values::<usize, i32>("up 3\ndown 8", keyword, ("direction", "amount")).unwrap(), vec![(2, 3), (4, 8)]);
+/// assert!(keyword_values::<usize, i32>("left 3", keyword, ("direction", "amount")).is_err());
+/// ```
+pub fn keyword_values<K, V: FromStr>(source: &str, keyword: impl Fn(&str) -> Option<K>, names: (&str, &str)) -> Result<Vec<(K, V)>> {
+
+    let (keyword_name, value_name) = names;
+    return source.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()).map(
+        |(line_idx, line)| {
+            let line_number = line_idx + 1;
+            let mut words = line.split_whitespace();
+
+            let word = words.next().ok_or_else(|| Error::parse(line_number, 1, format!("missing {}", keyword_name)))?;
+            let key = keyword(word).ok_or_else(
+                || Error::parse_token(line_number, line, word, format!("invalid {} '{}'", keyword_name, word))
+            )?;
+            let value = words.next().ok_or_else(
+                || Error::parse(line_number, line.trim_end().chars().count() + 2, format!("missing {}", value_name))
+            )?;
+            let value = token(line_number, line, value, value_name)?;
+            if let Some(extra) = words.next() {
+                return Err(Error::parse_token(line_number, line, extra, format!("unexpected '{}'", extra)));
+            }
+
+            return Ok((key, value));
+        }
+    ).collect();
+
+}
+
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_rng() {
+
+        let mut rng = Rng::new(2021);
+        for _ in 0..1000 {
+            assert!(rng.below(5) < 5);
+            assert!((-3..=3).contains(&rng.between(-3, 3)));
+        }
+
+        let mut items: Vec<u32> = (0..50).collect();
+        rng.shuffle(&mut items);
+        assert_ne!(items, (0..50).collect::<Vec<u32>>());
+        items.sort();
+        assert_eq!(items, (0..50).collect::<Vec<u32>>());
+
+    }
+
     #[test]
     fn test_basic() {
 
@@ -50,7 +528,7 @@ mod tests {
             b
         ";
         let y = "a\nb";
-        let z = remove_multiline_whitespace(&x);
+        let z = remove_multiline_whitespace(x);
 
         assert_ne!(x, y);  // sanity check
         assert_eq!(y, z);
@@ -69,11 +547,100 @@ mod tests {
             d
         ";
         let y = "a\n\nb\nc\n\nd";
-        let z = remove_multiline_whitespace(&x);
+        let z = remove_multiline_whitespace(x);
 
         assert_ne!(x, y);  // sanity check
         assert_eq!(y, z);
 
     }
 
-}
\ No newline at end of file
+    fn digits() -> Grid<u32> {
+        return Grid::parse_chars("123\n456\n", 1, |c| c.to_digit(10).ok_or(format!("invalid digit '{}'", c))).unwrap();
+    }
+
+    #[test]
+    fn test_grid_access() {
+
+        let mut grid = digits();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(1, 2), Some(&6));
+        assert_eq!(grid.get(2, 0), None);
+        assert_eq!(grid.get(0, 3), None);
+        assert_eq!(grid.row(1), Some(&[4, 5, 6][..]));
+        assert!(grid.row(2).is_none());
+        assert!(grid.column(3).is_none());
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.column(1).unwrap().copied().collect::<Vec<u32>>(), vec![2, 5]);
+        assert_eq!(grid.columns().map(|c| c.sum()).collect::<Vec<u32>>(), vec![5, 7, 9]);
+        assert_eq!(grid.positions().nth(4), Some(((1, 1), &5)));
+
+        grid[(0, 0)] = 9;
+        assert_eq!(grid.iter().sum::<u32>(), 29);
+        assert_eq!(grid.map(|&d| d % 2 == 0).iter().filter(|&&even| even).count(), 3);
+
+    }
+
+    #[test]
+    fn test_grid_neighbours() {
+
+        let grid = digits();
+        assert_eq!(grid.neighbours4(0, 0).collect::<Vec<_>>(), vec![(0, 1), (1, 0)]);
+        assert_eq!(grid.neighbours4(1, 1).collect::<Vec<_>>(), vec![(0, 1), (1, 0), (1, 2)]);
+        assert_eq!(grid.neighbours8(0, 2).collect::<Vec<_>>(), vec![(0, 1), (1, 1), (1, 2)]);
+        assert_eq!(grid.neighbours8(1, 1).count(), 5);
+
+    }
+
+    #[test]
+    fn test_grid_transpose_and_display() {
+
+        let grid = digits();
+        assert_eq!(grid.to_string(), "123\n456\n");
+        assert_eq!(grid.transpose().to_string(), "14\n25\n36\n");
+        assert_eq!(grid.transpose().transpose(), grid);
+
+        let wide = Grid::from_rows(vec![vec![1, 22], vec![333, 4]]).unwrap();
+        assert_eq!(wide.to_string(), "  1  22\n333   4\n");
+        assert!(Grid::from_rows(vec![vec![1, 2], vec![3]]).is_none());
+        assert!(Grid::<u32>::from_rows(vec![vec![], vec![]]).is_none());
+        assert_eq!(Grid::<u32>::from_rows(vec![]).map(|g| g.rows().count()), Some(0));
+
+    }
+
+    #[test]
+    fn test_grid_parse_errors() {
+
+        let number = |n: &str| n.parse::<u32>().map_err(|_| format!("invalid number '{}'", n));
+        assert!(matches!(Grid::parse_words("1 2\n3 x", 5, number), Err(Error::Parse { line: 6, column: 3, .. })));
+        assert!(matches!(Grid::parse_words("1 2\n\n3", 1, number), Err(Error::Parse { line: 3, column: 2, .. })));
+        assert!(matches!(Grid::parse_words("1 2\n3  4 5", 1, number), Err(Error::Parse { line: 2, column: 6, .. })));
+        assert_eq!(Grid::parse_words("", 1, number).unwrap().height(), 0);
+
+    }
+
+    #[test]
+    fn test_parse_errors() {
+
+        assert!(matches!(numbers_per_line::<i32>("1\n 2x", "depth"), Err(Error::Parse { line: 2, column: 2, .. })));
+        assert!(matches!(numbers_per_line::<i32>("1\n\n3\nx", "depth"), Err(Error::Parse { line: 4, column: 1, .. })));
+        assert_eq!(numbers_per_line::<i32>(" \n\t\n", "depth").unwrap(), vec![]);
+        assert!(matches!(comma_separated::<u32>(4, "1,2, x", "number"), Err(Error::Parse { line: 4, column: 6, .. })));
+        assert!(matches!(matrix::<u32>("1 2\n3 y", 7, "number"), Err(Error::Parse { line: 8, column: 3, .. })));
+
+        let keyword = |k: &str| if k == "up" { Some(()) } else { None };
+        let names = ("direction", "amount");
+        assert!(matches!(keyword_values::<(), i32>("up", keyword, names), Err(Error::Parse { line: 1, column: 4, .. })));
+        assert!(matches!(keyword_values::<(), i32>("up 1 2", keyword, names), Err(Error::Parse { line: 1, column: 6, .. })));
+        assert!(matches!(keyword_values::<(), i32>("up 1\n", keyword, names), Ok(v) if v.len() == 1));
+        assert!(matches!(keyword_values::<(), i32>("\nup 1\n \n\nup 2\n\n", keyword, names), Ok(v) if v.len() == 2));
+        assert!(matches!(keyword_values::<(), i32>("up 1\n\nup", keyword, names), Err(Error::Parse { line: 3, column: 4, .. })));
+
+    }
+
+    #[test]
+    fn test_blocks() {
+        assert_eq!(blocks(""), vec![]);
+        assert_eq!(blocks("\n\na\r\nb\r\n\r\nc"), vec![(3, "a\r\nb"), (6, "c")]);
+    }
+
+}
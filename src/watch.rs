@@ -0,0 +1,74 @@
+//! Noticing when an input file changes, by polling its modification time
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+
+/// How long to wait between checks of the file
+pub const INTERVAL: Duration = Duration::from_millis(500);
+
+
+/// Escape sequence that clears the terminal and moves the cursor to the top
+pub const CLEAR: &str = "\x1b[2J\x1b[H";
+
+
+/// Watches one file for changes
+pub struct Watcher {
+    path: PathBuf,
+
+    /// Modification time seen by the last poll, if there was one; the file
+    /// not existing counts as a time of its own
+    seen: Option<Option<SystemTime>>,
+}
+
+
+impl Watcher {
+
+    pub fn new(path: &Path) -> Watcher {
+        return Watcher { path: path.to_path_buf(), seen: None };
+    }
+
+    /// Whether the file changed (or appeared, or disappeared) since the last
+    /// poll. The first poll always counts as a change.
+    pub fn poll(&mut self) -> bool {
+        let modified = fs::metadata(&self.path).and_then(|m| m.modified()).ok();
+        let changed = self.seen != Some(modified);
+        self.seen = Some(modified);
+        return changed;
+    }
+
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use std::time::UNIX_EPOCH;
+
+    #[test]
+    fn test_poll() {
+
+        let path = std::env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mut watcher = Watcher::new(&path);
+        assert!(watcher.poll());
+        assert!(!watcher.poll());
+
+        fs::write(&path, "1\n").unwrap();
+        let file = File::options().write(true).open(&path).unwrap();
+        file.set_modified(UNIX_EPOCH + Duration::from_secs(1)).unwrap();
+        assert!(watcher.poll());
+        assert!(!watcher.poll());
+
+        file.set_modified(UNIX_EPOCH + Duration::from_secs(2)).unwrap();
+        assert!(watcher.poll());
+
+        drop(file);
+        fs::remove_file(&path).unwrap();
+        assert!(watcher.poll());
+
+    }
+
+}